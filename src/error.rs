@@ -9,6 +9,34 @@ pub enum MatrixError {
     InvalidDimensions,
     #[error("An attempt to access a non-existent index was made.")]
     UndefinedIndex,
+    #[error("The matrix is singular.")]
+    Singular,
+    #[error("The iteration did not converge.")]
+    NoConvergence,
+    #[error("Invalid operation: {0}.")]
+    InvalidOperation(String),
+    #[error("The indices do not form a permutation.")]
+    InvalidPermutation,
+    #[error("The matrix is not triangular.")]
+    NotTriangular,
+    #[error("Expected a {}x{} matrix, found a {}x{} one.", expected.0, expected.1, found.0, found.1)]
+    ShapeMismatch {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    #[error("Malformed cell {content:?} at line {line}, column {column}.")]
+    MalformedCell {
+        line: usize,
+        column: usize,
+        content: String,
+    },
+    #[error("Invalid file format: {0}")]
+    InvalidFormat(String),
+    /// Only returned with the `parallel` feature, but always present so the feature stays additive.
+    #[error("Failed to configure the thread pool: {0}")]
+    ThreadPool(String),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
 }
 
 #[derive(Error, Debug)]
@@ -16,4 +44,6 @@ pub enum MatrixError {
 pub enum VectorError {
     #[error("Expected an N-dimensional Vector, recieved different dimensions.")]
     InvalidDimensions,
+    #[error("Vector {index} is linearly dependent on the vectors before it.")]
+    LinearlyDependent { index: usize },
 }
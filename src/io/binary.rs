@@ -0,0 +1,460 @@
+//! A compact, self-describing binary format for caching matrices and vectors.
+//!
+//! Every file starts with a 28 byte header, with all header fields stored in little endian:
+//!
+//! | Bytes   | Contents                                          |
+//! |---------|---------------------------------------------------|
+//! | 0..4    | The magic bytes `MXLB`                            |
+//! | 4       | Format version, currently `1`                     |
+//! | 5       | Entry type, `0` for `f64` and `1` for `f32`       |
+//! | 6       | Endianness of the entries, `0` little and `1` big |
+//! | 7       | Reserved, always `0`                              |
+//! | 8..16   | Number of rows as a `u64`                         |
+//! | 16..24  | Number of columns as a `u64`                      |
+//! | 24..28  | CRC-32 checksum of the entries as a `u32`         |
+//!
+//! The entries follow the header in row-major order. Vectors are stored as `N` x 1 matrices.
+
+use std::io::{Read, Write};
+
+use crate::{error::MatrixError, matrix::Matrix, vector::VectorN};
+
+const MAGIC: &[u8] = b"MXLB";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = 28;
+
+/// Lookup table for the CRC-32 (IEEE) checksum.
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+
+        table[i] = crc;
+        i += 1;
+    }
+
+    table
+};
+
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, &byte| {
+        CRC_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+fn invalid(message: &str) -> MatrixError {
+    MatrixError::InvalidFormat(message.to_string())
+}
+
+/// The decoded fixed-size header.
+#[derive(Debug, Clone, Copy)]
+struct Header {
+    entry_size: usize,
+    little_endian: bool,
+    rows: usize,
+    columns: usize,
+    checksum: u32,
+}
+
+impl Header {
+    fn parse(bytes: &[u8]) -> Result<Self, MatrixError> {
+        if bytes.len() < HEADER_LEN || &bytes[..4] != MAGIC {
+            return Err(invalid("not a matrixlib binary file"));
+        }
+
+        if bytes[4] != VERSION {
+            return Err(MatrixError::InvalidFormat(format!(
+                "unsupported version {}",
+                bytes[4]
+            )));
+        }
+
+        let entry_size = match bytes[5] {
+            0 => 8,
+            1 => 4,
+            _ => return Err(invalid("unknown entry type")),
+        };
+
+        let little_endian = match bytes[6] {
+            0 => true,
+            1 => false,
+            _ => return Err(invalid("unknown endianness")),
+        };
+
+        Ok(Self {
+            entry_size,
+            little_endian,
+            rows: usize::try_from(u64::from_le_bytes(bytes[8..16].try_into().unwrap()))
+                .map_err(|_| invalid("too many rows"))?,
+            columns: usize::try_from(u64::from_le_bytes(bytes[16..24].try_into().unwrap()))
+                .map_err(|_| invalid("too many columns"))?,
+            checksum: u32::from_le_bytes(bytes[24..28].try_into().unwrap()),
+        })
+    }
+
+    /// Returns the length of the payload in bytes, which comes from an untrusted header and may overflow.
+    fn payload_len(&self) -> Result<usize, MatrixError> {
+        self.rows
+            .checked_mul(self.columns)
+            .and_then(|entries| entries.checked_mul(self.entry_size))
+            .ok_or_else(|| invalid("payload too large"))
+    }
+
+    /// Decodes the `i`th entry of the payload.
+    fn decode(&self, payload: &[u8], i: usize) -> f64 {
+        let bytes = &payload[i * self.entry_size..(i + 1) * self.entry_size];
+
+        match (self.entry_size, self.little_endian) {
+            (8, true) => f64::from_le_bytes(bytes.try_into().unwrap()),
+            (8, false) => f64::from_be_bytes(bytes.try_into().unwrap()),
+            (_, true) => f32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+            (_, false) => f32::from_be_bytes(bytes.try_into().unwrap()) as f64,
+        }
+    }
+
+    fn verify(&self, payload: &[u8]) -> Result<(), MatrixError> {
+        if crc32(payload) != self.checksum {
+            return Err(invalid("checksum mismatch"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes `f64` entries in native endianness, preceded by a header.
+fn write_binary(
+    writer: &mut impl Write,
+    rows: usize,
+    columns: usize,
+    data: &[f64],
+) -> Result<(), MatrixError> {
+    let payload: Vec<u8> = data.iter().flat_map(|f| f.to_ne_bytes()).collect();
+    let endianness = if cfg!(target_endian = "little") { 0 } else { 1 };
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[VERSION, 0, endianness, 0])?;
+    writer.write_all(&(rows as u64).to_le_bytes())?;
+    writer.write_all(&(columns as u64).to_le_bytes())?;
+    writer.write_all(&crc32(&payload).to_le_bytes())?;
+    writer.write_all(&payload)?;
+
+    Ok(())
+}
+
+/// Reads a header and its checked payload, returning the decoded entries.
+///
+/// The stored shape is checked with `check_shape` before anything is allocated for the payload.
+fn read_binary(
+    reader: &mut impl Read,
+    check_shape: fn(&Header) -> Result<(), MatrixError>,
+) -> Result<(Header, Vec<f64>), MatrixError> {
+    let mut header = [0u8; HEADER_LEN];
+    reader.read_exact(&mut header)?;
+    let header = Header::parse(&header)?;
+    check_shape(&header)?;
+
+    let mut payload = vec![0u8; header.payload_len()?];
+    reader.read_exact(&mut payload)?;
+    header.verify(&payload)?;
+
+    let data = (0..header.rows * header.columns)
+        .map(|i| header.decode(&payload, i))
+        .collect();
+
+    Ok((header, data))
+}
+
+/// Parses the header at the start of `bytes`, checks its shape with `check_shape` and returns it along
+/// with its checked payload.
+fn split_binary(
+    bytes: &[u8],
+    check_shape: fn(&Header) -> Result<(), MatrixError>,
+) -> Result<(Header, &[u8]), MatrixError> {
+    let header = Header::parse(bytes)?;
+    check_shape(&header)?;
+
+    let end = HEADER_LEN
+        .checked_add(header.payload_len()?)
+        .ok_or_else(|| invalid("payload too large"))?;
+    let payload = bytes
+        .get(HEADER_LEN..end)
+        .ok_or_else(|| invalid("truncated payload"))?;
+    header.verify(payload)?;
+
+    Ok((header, payload))
+}
+
+#[derive(Debug, Clone, Copy)]
+/// A read-only view of an `R` x `C` matrix stored in the binary format, borrowing its entries from a byte slice.
+pub struct MatrixView<'a, const R: usize, const C: usize> {
+    header: Header,
+    payload: &'a [u8],
+}
+
+impl<'a, const R: usize, const C: usize> MatrixView<'a, R, C> {
+    /// Fetches the entry located at row `i` and column `j`.
+    pub fn get(&self, i: usize, j: usize) -> Option<f64> {
+        if i >= R || j >= C {
+            return None;
+        }
+
+        Some(self.header.decode(self.payload, i * C + j))
+    }
+
+    /// Returns an iterator over the entries in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = f64> + 'a {
+        let header = self.header;
+        let payload = self.payload;
+
+        (0..R * C).map(move |i| header.decode(payload, i))
+    }
+
+    /// Copies the entries into an owned `Matrix`.
+    pub fn to_matrix(&self) -> Matrix<R, C> {
+        Matrix::new(self.iter().collect()).unwrap()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+/// A read-only view of an `N`-dimensional vector stored in the binary format, borrowing its entries from a byte slice.
+pub struct VectorView<'a, const N: usize> {
+    header: Header,
+    payload: &'a [u8],
+}
+
+impl<'a, const N: usize> VectorView<'a, N> {
+    /// Fetches the `i`th entry.
+    pub fn get(&self, i: usize) -> Option<f64> {
+        if i >= N {
+            return None;
+        }
+
+        Some(self.header.decode(self.payload, i))
+    }
+
+    /// Returns an iterator over the entries.
+    pub fn iter(&self) -> impl Iterator<Item = f64> + 'a {
+        let header = self.header;
+        let payload = self.payload;
+
+        (0..N).map(move |i| header.decode(payload, i))
+    }
+
+    /// Copies the entries into an owned `VectorN`.
+    pub fn to_vector(&self) -> VectorN<N> {
+        let data: Vec<f64> = self.iter().collect();
+
+        VectorN::new(data.try_into().unwrap())
+    }
+}
+
+/// Checks that a stored shape matches an `R` x `C` matrix.
+fn check_matrix_shape<const R: usize, const C: usize>(header: &Header) -> Result<(), MatrixError> {
+    if (header.rows, header.columns) != (R, C) {
+        return Err(MatrixError::ShapeMismatch {
+            expected: (R, C),
+            found: (header.rows, header.columns),
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks that a stored shape matches an `N`-dimensional vector, stored either as a column or a row.
+fn check_vector_shape<const N: usize>(header: &Header) -> Result<(), MatrixError> {
+    match (header.rows, header.columns) {
+        (n, 1) | (1, n) if n == N => Ok(()),
+        found => Err(MatrixError::ShapeMismatch {
+            expected: (N, 1),
+            found,
+        }),
+    }
+}
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    /// Writes the matrix in the binary format.
+    pub fn write_to(&self, writer: &mut impl Write) -> Result<(), MatrixError> {
+        write_binary(writer, R, C, self.data())
+    }
+
+    /// Reads a matrix in the binary format, checking its shape and checksum.
+    pub fn read_from(reader: &mut impl Read) -> Result<Self, MatrixError> {
+        let (_, data) = read_binary(reader, check_matrix_shape::<R, C>)?;
+
+        Matrix::new(data)
+    }
+
+    /// Creates a view of a matrix in the binary format without copying its entries.
+    pub fn view_bytes(bytes: &[u8]) -> Result<MatrixView<'_, R, C>, MatrixError> {
+        let (header, payload) = split_binary(bytes, check_matrix_shape::<R, C>)?;
+
+        Ok(MatrixView { header, payload })
+    }
+}
+
+impl<const N: usize> VectorN<N> {
+    /// Writes the vector in the binary format.
+    pub fn write_to(&self, writer: &mut impl Write) -> Result<(), MatrixError> {
+        write_binary(writer, N, 1, self.data())
+    }
+
+    /// Reads a vector in the binary format, checking its shape and checksum.
+    pub fn read_from(reader: &mut impl Read) -> Result<Self, MatrixError> {
+        let (_, data) = read_binary(reader, check_vector_shape::<N>)?;
+
+        Ok(VectorN::new(data.try_into().unwrap()))
+    }
+
+    /// Creates a view of a vector in the binary format without copying its entries.
+    pub fn view_bytes(bytes: &[u8]) -> Result<VectorView<'_, N>, MatrixError> {
+        let (header, payload) = split_binary(bytes, check_vector_shape::<N>)?;
+
+        Ok(VectorView { header, payload })
+    }
+}
+
+#[cfg(test)]
+mod binary_tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_matrix_round_trip() {
+        let matrix: Matrix<2, 3> = Matrix::new(vec![1.0, -2.5, 3.0, f64::MAX, 0.1, 6.0]).unwrap();
+
+        let mut bytes = Vec::new();
+        matrix.write_to(&mut bytes).unwrap();
+
+        assert_eq!(bytes.len(), HEADER_LEN + 6 * 8);
+
+        let read: Matrix<2, 3> = Matrix::read_from(&mut bytes.as_slice()).unwrap();
+
+        assert_eq!(read, matrix);
+    }
+
+    #[test]
+    fn test_vector_round_trip() {
+        let vector = VectorN::new([1.0, 2.0, 3.0]);
+
+        let mut bytes = Vec::new();
+        vector.write_to(&mut bytes).unwrap();
+
+        let read: VectorN<3> = VectorN::read_from(&mut bytes.as_slice()).unwrap();
+
+        assert_eq!(read, vector);
+    }
+
+    #[test]
+    fn test_views() {
+        let matrix: Matrix<2, 2> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+
+        let mut bytes = Vec::new();
+        matrix.write_to(&mut bytes).unwrap();
+
+        let view = Matrix::<2, 2>::view_bytes(&bytes).unwrap();
+
+        assert_eq!(view.get(1, 0), Some(3.0));
+        assert_eq!(view.get(2, 0), None);
+        assert_eq!(view.to_matrix(), matrix);
+
+        let vector = VectorN::new([5.0, 6.0]);
+
+        let mut bytes = Vec::new();
+        vector.write_to(&mut bytes).unwrap();
+
+        let view = VectorN::<2>::view_bytes(&bytes).unwrap();
+
+        assert_eq!(view.iter().collect::<Vec<f64>>(), [5.0, 6.0]);
+        assert_eq!(view.to_vector(), vector);
+    }
+
+    #[test]
+    fn test_big_endian_f32() {
+        let payload: Vec<u8> = [1.5f32, -2.0]
+            .iter()
+            .flat_map(|f| f.to_be_bytes())
+            .collect();
+
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&[VERSION, 1, 1, 0]);
+        bytes.extend_from_slice(&1u64.to_le_bytes());
+        bytes.extend_from_slice(&2u64.to_le_bytes());
+        bytes.extend_from_slice(&crc32(&payload).to_le_bytes());
+        bytes.extend_from_slice(&payload);
+
+        let matrix: Matrix<1, 2> = Matrix::read_from(&mut bytes.as_slice()).unwrap();
+        assert_eq!(matrix.data(), &[1.5, -2.0]);
+
+        let vector: VectorN<2> = VectorN::read_from(&mut bytes.as_slice()).unwrap();
+        assert_eq!(vector.data(), &[1.5, -2.0]);
+    }
+
+    #[test]
+    fn test_corruption_is_detected() {
+        let matrix: Matrix<2, 2> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+
+        let mut bytes = Vec::new();
+        matrix.write_to(&mut bytes).unwrap();
+
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+
+        assert!(matches!(
+            Matrix::<2, 2>::read_from(&mut bytes.as_slice()),
+            Err(MatrixError::InvalidFormat(_))
+        ));
+        assert!(Matrix::<2, 2>::view_bytes(&bytes).is_err());
+
+        assert!(Matrix::<2, 2>::view_bytes(&bytes[..20]).is_err());
+    }
+
+    #[test]
+    fn test_shape_mismatch() {
+        let matrix: Matrix<2, 2> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+
+        let mut bytes = Vec::new();
+        matrix.write_to(&mut bytes).unwrap();
+
+        assert!(matches!(
+            Matrix::<1, 4>::read_from(&mut bytes.as_slice()),
+            Err(MatrixError::ShapeMismatch {
+                expected: (1, 4),
+                found: (2, 2)
+            })
+        ));
+        assert!(VectorN::<4>::view_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_hostile_shape_is_rejected_before_allocating() {
+        let matrix: Matrix<2, 2> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+
+        let mut bytes = Vec::new();
+        matrix.write_to(&mut bytes).unwrap();
+        bytes[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        bytes[16..24].copy_from_slice(&(u64::MAX / 2).to_le_bytes());
+
+        assert!(matches!(
+            Matrix::<2, 2>::read_from(&mut bytes.as_slice()),
+            Err(MatrixError::ShapeMismatch { .. }) | Err(MatrixError::InvalidFormat(_))
+        ));
+        assert!(Matrix::<2, 2>::view_bytes(&bytes).is_err());
+        assert!(VectorN::<4>::read_from(&mut bytes.as_slice()).is_err());
+    }
+}
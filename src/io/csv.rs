@@ -0,0 +1,423 @@
+//! CSV and other delimited-text formats.
+
+use std::io::{BufRead, Write};
+
+use crate::{error::MatrixError, matrix::Matrix, vector::VectorN};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// How empty cells are handled when reading and writing delimited text.
+pub enum EmptyCell {
+    /// Empty cells are reported as malformed.
+    Error,
+    /// Empty cells are read as `NaN`, and `NaN` entries are written as empty cells.
+    Nan,
+    /// Empty cells are read as the given value.
+    Fill(f64),
+}
+
+#[derive(Debug, Clone)]
+/// Options for reading and writing delimited text.
+pub struct CsvOptions {
+    /// The character separating cells on a line.
+    pub delimiter: char,
+    /// Whether the first (non-comment) line is a header row that should be skipped.
+    pub has_header: bool,
+    /// Lines starting with this character are ignored.
+    pub comment: Option<char>,
+    /// What to do with empty cells.
+    pub empty_cell: EmptyCell,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            has_header: false,
+            comment: None,
+            empty_cell: EmptyCell::Error,
+        }
+    }
+}
+
+/// Reads every data row of the input into a vector of parsed cells.
+fn read_rows(reader: impl BufRead, options: &CsvOptions) -> Result<Vec<Vec<f64>>, MatrixError> {
+    let mut rows = Vec::new();
+    let mut header_skipped = !options.has_header;
+
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        let trimmed = line.trim();
+
+        // Skip blank lines and comments.
+        if trimmed.is_empty() || options.comment.is_some_and(|c| trimmed.starts_with(c)) {
+            continue;
+        }
+
+        if !header_skipped {
+            header_skipped = true;
+            continue;
+        }
+
+        let row = split_cells(&line, options.delimiter)
+            .enumerate()
+            .map(|(j, cell)| parse_cell(cell, i + 1, j + 1, options))
+            .collect::<Result<Vec<f64>, MatrixError>>()?;
+
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+/// Splits a line at every delimiter that is not inside double quotes, keeping the quotes.
+fn split_cells(line: &str, delimiter: char) -> impl Iterator<Item = &str> {
+    let mut quoted = false;
+
+    line.split(move |c| {
+        if c == '"' {
+            quoted = !quoted;
+        }
+
+        c == delimiter && !quoted
+    })
+}
+
+/// Parses a single cell located at the given (1-based) line and column.
+fn parse_cell(
+    cell: &str,
+    line: usize,
+    column: usize,
+    options: &CsvOptions,
+) -> Result<f64, MatrixError> {
+    let content = cell.trim();
+
+    // Spreadsheets like to quote everything, so strip a surrounding pair of quotes.
+    let content = content
+        .strip_prefix('"')
+        .and_then(|c| c.strip_suffix('"'))
+        .unwrap_or(content)
+        .trim();
+
+    let malformed = || MatrixError::MalformedCell {
+        line,
+        column,
+        content: cell.to_string(),
+    };
+
+    if content.is_empty() {
+        return match options.empty_cell {
+            EmptyCell::Error => Err(malformed()),
+            EmptyCell::Nan => Ok(f64::NAN),
+            EmptyCell::Fill(value) => Ok(value),
+        };
+    }
+
+    content.parse().map_err(|_| malformed())
+}
+
+/// Formats a single entry, honouring the empty cell policy for `NaN`s.
+fn format_cell(entry: f64, options: &CsvOptions) -> String {
+    if entry.is_nan() && options.empty_cell == EmptyCell::Nan {
+        String::new()
+    } else {
+        entry.to_string()
+    }
+}
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    /// Reads a matrix from delimited text, one row per line.
+    pub fn from_csv_reader(
+        reader: impl BufRead,
+        options: &CsvOptions,
+    ) -> Result<Self, MatrixError> {
+        let rows = read_rows(reader, options)?;
+
+        if rows.len() != R {
+            let columns = rows.first().map_or(0, |row| row.len());
+            return Err(MatrixError::ShapeMismatch {
+                expected: (R, C),
+                found: (rows.len(), columns),
+            });
+        }
+
+        if let Some(row) = rows.iter().find(|row| row.len() != C) {
+            return Err(MatrixError::ShapeMismatch {
+                expected: (R, C),
+                found: (R, row.len()),
+            });
+        }
+
+        Matrix::new(rows.concat())
+    }
+
+    /// Writes the matrix as delimited text, optionally preceded by a header row of column names.
+    pub fn to_csv_writer<W: Write>(
+        &self,
+        mut writer: W,
+        header: Option<&[&str]>,
+        options: &CsvOptions,
+    ) -> Result<(), MatrixError> {
+        let delimiter = options.delimiter.to_string();
+
+        if let Some(header) = header {
+            if header.len() != C {
+                return Err(MatrixError::ShapeMismatch {
+                    expected: (1, C),
+                    found: (1, header.len()),
+                });
+            }
+
+            writeln!(writer, "{}", header.join(&delimiter))?;
+        }
+
+        // Zero columns give one empty line per row, where `chunks(C)` would panic.
+        for i in 0..R {
+            let row = &self.data()[i * C..(i + 1) * C];
+            let cells: Vec<String> = row.iter().map(|&f| format_cell(f, options)).collect();
+
+            writeln!(writer, "{}", cells.join(&delimiter))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<const N: usize> VectorN<N> {
+    /// Reads a vector from delimited text laid out either as a single column or a single row.
+    pub fn from_csv_reader(
+        reader: impl BufRead,
+        options: &CsvOptions,
+    ) -> Result<Self, MatrixError> {
+        let rows = read_rows(reader, options)?;
+
+        let data: Vec<f64> = match rows.as_slice() {
+            [row] if row.len() == N => row.clone(),
+            _ if rows.len() == N && rows.iter().all(|row| row.len() == 1) => rows.concat(),
+            _ => {
+                let columns = rows.first().map_or(0, |row| row.len());
+                return Err(MatrixError::ShapeMismatch {
+                    expected: (N, 1),
+                    found: (rows.len(), columns),
+                });
+            }
+        };
+
+        Ok(VectorN::new(data.try_into().unwrap()))
+    }
+
+    /// Writes the vector as a single column of delimited text, optionally preceded by a header.
+    pub fn to_csv_writer<W: Write>(
+        &self,
+        mut writer: W,
+        header: Option<&str>,
+        options: &CsvOptions,
+    ) -> Result<(), MatrixError> {
+        if let Some(header) = header {
+            writeln!(writer, "{}", header)?;
+        }
+
+        for &entry in self.data() {
+            writeln!(writer, "{}", format_cell(entry, options))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod csv_tests {
+    use super::*;
+
+    #[test]
+    fn test_read_matrix() {
+        let input = "1,2,3\n4,5,6\n";
+
+        let matrix: Matrix<2, 3> =
+            Matrix::from_csv_reader(input.as_bytes(), &CsvOptions::default()).unwrap();
+
+        assert_eq!(matrix.data(), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    }
+
+    #[test]
+    fn test_read_with_header_comments_and_delimiter() {
+        let input = "# exported from a spreadsheet\na;b\n\n\"1.5\"; 2\n# halfway there\n3;  -4e1\n";
+
+        let options = CsvOptions {
+            delimiter: ';',
+            has_header: true,
+            comment: Some('#'),
+            ..Default::default()
+        };
+
+        let matrix: Matrix<2, 2> = Matrix::from_csv_reader(input.as_bytes(), &options).unwrap();
+
+        assert_eq!(matrix.data(), &[1.5, 2.0, 3.0, -40.0]);
+    }
+
+    #[test]
+    fn test_quoted_delimiter() {
+        let input = "\"1\",\" -2.5 \"\n";
+
+        let matrix: Matrix<1, 2> =
+            Matrix::from_csv_reader(input.as_bytes(), &CsvOptions::default()).unwrap();
+        assert_eq!(matrix.data(), &[1.0, -2.5]);
+
+        // A decimal comma stays in one cell and is reported there instead of shifting the columns.
+        let input = "\"1,5\",2\n";
+
+        let result: Result<Matrix<1, 2>, _> =
+            Matrix::from_csv_reader(input.as_bytes(), &CsvOptions::default());
+        match result {
+            Err(MatrixError::MalformedCell {
+                line,
+                column,
+                content,
+            }) => {
+                assert_eq!((line, column), (1, 1));
+                assert_eq!(content, "\"1,5\"");
+            }
+            other => panic!("expected a malformed cell, got {:?}", other),
+        }
+
+        let result: Result<Matrix<1, 1>, _> =
+            Matrix::from_csv_reader("\"1,2\n".as_bytes(), &CsvOptions::default());
+        assert!(matches!(result, Err(MatrixError::MalformedCell { .. })));
+    }
+
+    #[test]
+    fn test_empty_cells() {
+        let input = "1,,3\n";
+
+        let result: Result<Matrix<1, 3>, _> =
+            Matrix::from_csv_reader(input.as_bytes(), &CsvOptions::default());
+        assert!(matches!(
+            result,
+            Err(MatrixError::MalformedCell {
+                line: 1,
+                column: 2,
+                ..
+            })
+        ));
+
+        let options = CsvOptions {
+            empty_cell: EmptyCell::Fill(0.0),
+            ..Default::default()
+        };
+        let matrix: Matrix<1, 3> = Matrix::from_csv_reader(input.as_bytes(), &options).unwrap();
+        assert_eq!(matrix.data(), &[1.0, 0.0, 3.0]);
+
+        let options = CsvOptions {
+            empty_cell: EmptyCell::Nan,
+            ..Default::default()
+        };
+        let matrix: Matrix<1, 3> = Matrix::from_csv_reader(input.as_bytes(), &options).unwrap();
+        assert!(matrix.data()[1].is_nan());
+    }
+
+    #[test]
+    fn test_malformed_cell_location() {
+        let input = "header\n1,2\n3,x\n";
+
+        let options = CsvOptions {
+            has_header: true,
+            ..Default::default()
+        };
+
+        let result: Result<Matrix<2, 2>, _> = Matrix::from_csv_reader(input.as_bytes(), &options);
+
+        match result {
+            Err(MatrixError::MalformedCell {
+                line,
+                column,
+                content,
+            }) => {
+                assert_eq!((line, column), (3, 2));
+                assert_eq!(content, "x");
+            }
+            other => panic!("expected a malformed cell, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_wrong_shape() {
+        let input = "1,2\n3\n";
+
+        let result: Result<Matrix<2, 2>, _> =
+            Matrix::from_csv_reader(input.as_bytes(), &CsvOptions::default());
+
+        assert!(matches!(result, Err(MatrixError::ShapeMismatch { .. })));
+    }
+
+    #[test]
+    fn test_matrix_round_trip() {
+        let matrix: Matrix<2, 2> = Matrix::new(vec![0.1, f64::NAN, -3.0, 2.5]).unwrap();
+
+        let options = CsvOptions {
+            delimiter: '\t',
+            has_header: true,
+            empty_cell: EmptyCell::Nan,
+            ..Default::default()
+        };
+
+        let mut output = Vec::new();
+        matrix
+            .to_csv_writer(&mut output, Some(&["x", "y"]), &options)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output.clone()).unwrap(),
+            "x\ty\n0.1\t\n-3\t2.5\n"
+        );
+
+        let read: Matrix<2, 2> = Matrix::from_csv_reader(output.as_slice(), &options).unwrap();
+
+        assert_eq!(read.data()[0], 0.1);
+        assert!(read.data()[1].is_nan());
+        assert_eq!(read.data()[2..], [-3.0, 2.5]);
+    }
+
+    #[test]
+    fn test_write_without_columns() {
+        let matrix: Matrix<2, 0> = Matrix::new(vec![]).unwrap();
+
+        let mut output = Vec::new();
+        matrix
+            .to_csv_writer(&mut output, None, &CsvOptions::default())
+            .unwrap();
+
+        assert_eq!(output, b"\n\n");
+    }
+
+    #[test]
+    fn test_vector_round_trip() {
+        let vector = VectorN::new([1.0, 2.0, 3.0]);
+
+        let mut output = Vec::new();
+        vector
+            .to_csv_writer(&mut output, Some("v"), &CsvOptions::default())
+            .unwrap();
+
+        assert_eq!(output, b"v\n1\n2\n3\n");
+
+        let options = CsvOptions {
+            has_header: true,
+            ..Default::default()
+        };
+        let read: VectorN<3> = VectorN::from_csv_reader(output.as_slice(), &options).unwrap();
+
+        assert_eq!(read, vector);
+    }
+
+    #[test]
+    fn test_vector_as_row() {
+        let vector: VectorN<3> =
+            VectorN::from_csv_reader("1,2,3".as_bytes(), &CsvOptions::default()).unwrap();
+
+        assert_eq!(vector.data(), &[1.0, 2.0, 3.0]);
+
+        let result: Result<VectorN<2>, _> =
+            VectorN::from_csv_reader("1,2,3".as_bytes(), &CsvOptions::default());
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,5 @@
+//! Importing and exporting matrices and vectors.
+
+pub mod binary;
+pub mod csv;
+pub mod npy;
@@ -0,0 +1,584 @@
+//! NumPy `.npy` files and uncompressed `.npz` archives.
+
+use std::io::{Read, Write};
+
+use crate::{error::MatrixError, matrix::Matrix, vector::VectorN};
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+fn invalid(message: &str) -> MatrixError {
+    MatrixError::InvalidFormat(message.to_string())
+}
+
+/// Finds the value following `'key':` in the header dictionary.
+fn header_value<'a>(header: &'a str, key: &str) -> Result<&'a str, MatrixError> {
+    let pattern = format!("'{}':", key);
+
+    header
+        .find(&pattern)
+        .map(|i| header[i + pattern.len()..].trim_start())
+        .ok_or_else(|| MatrixError::InvalidFormat(format!("missing '{}' in header", key)))
+}
+
+/// Parses the header dictionary into (is little endian, size of an entry, fortran order, shape).
+fn parse_header(header: &str) -> Result<(bool, usize, bool, Vec<usize>), MatrixError> {
+    // The dtype, e.g. '<f8'
+    let descr = header_value(header, "descr")?;
+    let descr = descr
+        .strip_prefix('\'')
+        .and_then(|d| d.split('\'').next())
+        .ok_or_else(|| invalid("malformed 'descr' in header"))?;
+
+    let (little_endian, size) = match descr {
+        "<f8" => (true, 8),
+        ">f8" => (false, 8),
+        "=f8" | "|f8" => (cfg!(target_endian = "little"), 8),
+        "<f4" => (true, 4),
+        ">f4" => (false, 4),
+        "=f4" | "|f4" => (cfg!(target_endian = "little"), 4),
+        other => {
+            return Err(MatrixError::InvalidFormat(format!(
+                "unsupported dtype '{}', expected f8 or f4",
+                other
+            )))
+        }
+    };
+
+    let fortran_order = header_value(header, "fortran_order")?;
+    let fortran_order = if fortran_order.starts_with("True") {
+        true
+    } else if fortran_order.starts_with("False") {
+        false
+    } else {
+        return Err(invalid("malformed 'fortran_order' in header"));
+    };
+
+    // The shape, e.g. (2, 3) or (4,)
+    let shape = header_value(header, "shape")?;
+    let shape = shape
+        .strip_prefix('(')
+        .and_then(|s| s.split(')').next())
+        .ok_or_else(|| invalid("malformed 'shape' in header"))?;
+
+    let shape = shape
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse()
+                .map_err(|_| invalid("malformed 'shape' in header"))
+        })
+        .collect::<Result<Vec<usize>, MatrixError>>()?;
+
+    Ok((little_endian, size, fortran_order, shape))
+}
+
+/// Reads an array from the `.npy` format, returning its data in C (row-major) order.
+///
+/// The stored shape is checked with `check_shape` before anything is allocated for the data.
+fn read_npy(
+    mut reader: impl Read,
+    check_shape: fn(&[usize]) -> Result<(), MatrixError>,
+) -> Result<Vec<f64>, MatrixError> {
+    let mut preamble = [0u8; 8];
+    reader.read_exact(&mut preamble)?;
+
+    if &preamble[..6] != MAGIC {
+        return Err(invalid("not a .npy file"));
+    }
+
+    // Version 1.0 uses a 2 byte header length, 2.0 and 3.0 use 4 bytes.
+    let header_len = match preamble[6] {
+        1 => {
+            let mut len = [0u8; 2];
+            reader.read_exact(&mut len)?;
+            u16::from_le_bytes(len) as usize
+        }
+        2 | 3 => {
+            let mut len = [0u8; 4];
+            reader.read_exact(&mut len)?;
+            u32::from_le_bytes(len) as usize
+        }
+        version => {
+            return Err(MatrixError::InvalidFormat(format!(
+                "unsupported .npy version {}",
+                version
+            )))
+        }
+    };
+
+    // Read through `take` so a corrupt length cannot allocate gigabytes up front.
+    let mut header = Vec::new();
+    reader
+        .by_ref()
+        .take(header_len as u64)
+        .read_to_end(&mut header)?;
+    if header.len() != header_len {
+        return Err(invalid("truncated header"));
+    }
+    let header = String::from_utf8(header).map_err(|_| invalid("header is not valid UTF-8"))?;
+
+    let (little_endian, size, fortran_order, shape) = parse_header(&header)?;
+    check_shape(&shape)?;
+
+    let len = shape
+        .iter()
+        .try_fold(1usize, |len, &n| len.checked_mul(n))
+        .ok_or_else(|| invalid("array too large"))?;
+    let byte_len = len
+        .checked_mul(size)
+        .ok_or_else(|| invalid("array too large"))?;
+    let mut bytes = vec![0u8; byte_len];
+    reader.read_exact(&mut bytes)?;
+
+    let mut data: Vec<f64> = bytes
+        .chunks_exact(size)
+        .map(|chunk| match (size, little_endian) {
+            (8, true) => f64::from_le_bytes(chunk.try_into().unwrap()),
+            (8, false) => f64::from_be_bytes(chunk.try_into().unwrap()),
+            (_, true) => f32::from_le_bytes(chunk.try_into().unwrap()) as f64,
+            (_, false) => f32::from_be_bytes(chunk.try_into().unwrap()) as f64,
+        })
+        .collect();
+
+    // Fortran order is column-major, so transpose it into row-major order.
+    if fortran_order && shape.len() == 2 {
+        let (rows, columns) = (shape[0], shape[1]);
+        data = (0..len)
+            .map(|i| data[(i % columns) * rows + i / columns])
+            .collect();
+    } else if fortran_order && shape.len() > 2 {
+        return Err(invalid(
+            "fortran order is only supported for 1 and 2 dimensional arrays",
+        ));
+    }
+
+    Ok(data)
+}
+
+/// Writes little endian `f64` data in C order in the `.npy` format.
+fn write_npy(mut writer: impl Write, shape: &str, data: &[f64]) -> Result<(), MatrixError> {
+    let mut header = format!(
+        "{{'descr': '<f8', 'fortran_order': False, 'shape': {}, }}",
+        shape
+    );
+
+    // The header is padded with spaces and terminated with a newline so the data is 64 byte aligned.
+    let unpadded = MAGIC.len() + 4 + header.len() + 1;
+    header.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+    header.push('\n');
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[1, 0])?;
+    writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+
+    for entry in data {
+        writer.write_all(&entry.to_le_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// Adds an offset read from an archive, returning an `InvalidFormat` error if it overflows.
+fn add(base: usize, offset: usize) -> Result<usize, MatrixError> {
+    base.checked_add(offset)
+        .ok_or_else(|| invalid("offset out of range in .npz archive"))
+}
+
+/// Converts a 64-bit size or offset from an archive, returning an `InvalidFormat` error if it doesn't fit.
+fn to_usize(value: u64) -> Result<usize, MatrixError> {
+    usize::try_from(value).map_err(|_| invalid("offset out of range in .npz archive"))
+}
+
+fn read_bytes<const N: usize>(bytes: &[u8], offset: usize) -> Result<[u8; N], MatrixError> {
+    offset
+        .checked_add(N)
+        .and_then(|end| bytes.get(offset..end))
+        .map(|b| b.try_into().unwrap())
+        .ok_or_else(|| invalid("truncated .npz archive"))
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Result<u16, MatrixError> {
+    Ok(u16::from_le_bytes(read_bytes(bytes, offset)?))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, MatrixError> {
+    Ok(u32::from_le_bytes(read_bytes(bytes, offset)?))
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Result<u64, MatrixError> {
+    Ok(u64::from_le_bytes(read_bytes(bytes, offset)?))
+}
+
+/// Finds the contents of the array called `name` in a zip archive.
+fn find_npz_entry<'a>(archive: &'a [u8], name: &str) -> Result<&'a [u8], MatrixError> {
+    // NumPy stores each array as `<name>.npy`.
+    let file_name = if name.ends_with(".npy") {
+        name.to_string()
+    } else {
+        format!("{}.npy", name)
+    };
+
+    // Search backwards for the end of central directory record.
+    let eocd = (0..archive.len().saturating_sub(21))
+        .rev()
+        .find(|&i| archive[i..].starts_with(&[0x50, 0x4b, 0x05, 0x06]))
+        .ok_or_else(|| invalid("missing end of central directory record"))?;
+
+    let mut entries = read_u16(archive, eocd + 10)? as u64;
+    let mut offset = read_u32(archive, eocd + 16)? as u64;
+
+    // Zip64 archives keep the real values in a separate record pointed to by a locator.
+    if offset == 0xFFFF_FFFF && eocd >= 20 && read_u32(archive, eocd - 20)? == 0x0706_4b50 {
+        let zip64_eocd = to_usize(read_u64(archive, eocd - 12)?)?;
+        entries = read_u64(archive, add(zip64_eocd, 32)?)?;
+        offset = read_u64(archive, add(zip64_eocd, 48)?)?;
+    }
+
+    let mut offset = to_usize(offset)?;
+
+    for _ in 0..entries {
+        if read_u32(archive, offset)? != 0x0201_4b50 {
+            return Err(invalid("malformed central directory"));
+        }
+
+        // The signature was read, so `offset` lies within the archive and small additions can't overflow.
+        let method = read_u16(archive, offset + 10)?;
+        let mut compressed_size = read_u32(archive, offset + 20)? as u64;
+        let mut uncompressed_size = read_u32(archive, offset + 24)? as u64;
+        let name_len = read_u16(archive, offset + 28)? as usize;
+        let extra_len = read_u16(archive, offset + 30)? as usize;
+        let comment_len = read_u16(archive, offset + 32)? as usize;
+        let mut local_offset = read_u32(archive, offset + 42)? as u64;
+
+        let name_start = add(offset, 46)?;
+        let mut extra = add(name_start, name_len)?;
+        let entry_name = archive
+            .get(name_start..extra)
+            .ok_or_else(|| invalid("truncated .npz archive"))?;
+
+        // The zip64 extra field only contains the values that overflowed in the fixed fields.
+        let extra_end = add(extra, extra_len)?;
+        while add(extra, 4)? <= extra_end {
+            let id = read_u16(archive, extra)?;
+            let size = read_u16(archive, extra + 2)? as usize;
+
+            if id == 0x0001 {
+                let mut field = extra + 4;
+                for value in [
+                    &mut uncompressed_size,
+                    &mut compressed_size,
+                    &mut local_offset,
+                ] {
+                    if *value == 0xFFFF_FFFF {
+                        *value = read_u64(archive, field)?;
+                        field = add(field, 8)?;
+                    }
+                }
+            }
+
+            extra = add(extra + 4, size)?;
+        }
+
+        if entry_name == file_name.as_bytes() {
+            if method != 0 || compressed_size != uncompressed_size {
+                return Err(invalid("compressed .npz archives are not supported"));
+            }
+
+            let local_offset = to_usize(local_offset)?;
+            if read_u32(archive, local_offset)? != 0x0403_4b50 {
+                return Err(invalid("malformed local file header"));
+            }
+
+            let name_len = read_u16(archive, local_offset + 26)? as usize;
+            let extra_len = read_u16(archive, local_offset + 28)? as usize;
+            let start = add(add(local_offset, 30)?, name_len + extra_len)?;
+            let end = add(start, to_usize(compressed_size)?)?;
+
+            return archive
+                .get(start..end)
+                .ok_or_else(|| invalid("truncated .npz archive"));
+        }
+
+        offset = add(extra_end, comment_len)?;
+    }
+
+    Err(MatrixError::InvalidFormat(format!(
+        "no array named '{}' in archive",
+        name
+    )))
+}
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    /// Checks that the shape of an array in a `.npy` file matches the matrix.
+    fn check_npy_shape(shape: &[usize]) -> Result<(), MatrixError> {
+        match *shape {
+            [rows, columns] if (rows, columns) == (R, C) => Ok(()),
+            [rows, columns] => Err(MatrixError::ShapeMismatch {
+                expected: (R, C),
+                found: (rows, columns),
+            }),
+            _ => Err(MatrixError::InvalidFormat(format!(
+                "expected a 2 dimensional array, found {} dimensions",
+                shape.len()
+            ))),
+        }
+    }
+
+    /// Reads a matrix from a `.npy` file containing a 2 dimensional `f8` or `f4` array.
+    pub fn from_npy_reader(reader: impl Read) -> Result<Self, MatrixError> {
+        Matrix::new(read_npy(reader, Self::check_npy_shape)?)
+    }
+
+    /// Reads the matrix called `name` from an uncompressed `.npz` archive.
+    pub fn from_npz_reader(mut reader: impl Read, name: &str) -> Result<Self, MatrixError> {
+        let mut archive = Vec::new();
+        reader.read_to_end(&mut archive)?;
+
+        Matrix::new(read_npy(
+            find_npz_entry(&archive, name)?,
+            Self::check_npy_shape,
+        )?)
+    }
+
+    /// Writes the matrix as a little endian `f8` array in the `.npy` format.
+    pub fn to_npy_writer(&self, writer: impl Write) -> Result<(), MatrixError> {
+        write_npy(writer, &format!("({}, {})", R, C), self.data())
+    }
+}
+
+impl<const N: usize> VectorN<N> {
+    /// Checks that the shape of an array in a `.npy` file matches the vector.
+    fn check_npy_shape(shape: &[usize]) -> Result<(), MatrixError> {
+        match *shape {
+            [n] | [n, 1] | [1, n] if n == N => Ok(()),
+            [n] => Err(MatrixError::ShapeMismatch {
+                expected: (N, 1),
+                found: (n, 1),
+            }),
+            [rows, columns] => Err(MatrixError::ShapeMismatch {
+                expected: (N, 1),
+                found: (rows, columns),
+            }),
+            _ => Err(MatrixError::InvalidFormat(format!(
+                "expected a 1 or 2 dimensional array, found {} dimensions",
+                shape.len()
+            ))),
+        }
+    }
+
+    /// Reads a vector from a `.npy` file containing an `f8` or `f4` array of shape `(N,)`, `(N, 1)` or `(1, N)`.
+    pub fn from_npy_reader(reader: impl Read) -> Result<Self, MatrixError> {
+        let data = read_npy(reader, Self::check_npy_shape)?;
+
+        Ok(VectorN::new(data.try_into().unwrap()))
+    }
+
+    /// Reads the vector called `name` from an uncompressed `.npz` archive.
+    pub fn from_npz_reader(mut reader: impl Read, name: &str) -> Result<Self, MatrixError> {
+        let mut archive = Vec::new();
+        reader.read_to_end(&mut archive)?;
+
+        let data = read_npy(find_npz_entry(&archive, name)?, Self::check_npy_shape)?;
+
+        Ok(VectorN::new(data.try_into().unwrap()))
+    }
+
+    /// Writes the vector as a 1 dimensional little endian `f8` array in the `.npy` format.
+    pub fn to_npy_writer(&self, writer: impl Write) -> Result<(), MatrixError> {
+        write_npy(writer, &format!("({},)", N), self.data())
+    }
+}
+
+#[cfg(test)]
+mod npy_tests {
+    use super::*;
+
+    /// Builds an uncompressed zip archive the same way `numpy.savez` lays it out.
+    fn stored_zip(entries: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        let mut archive = Vec::new();
+        let mut central_directory = Vec::new();
+
+        for (name, data) in entries {
+            let offset = archive.len() as u32;
+
+            archive.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+            archive.extend_from_slice(&[20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+            archive.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            archive.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            archive.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            archive.extend_from_slice(&[0, 0]);
+            archive.extend_from_slice(name.as_bytes());
+            archive.extend_from_slice(data);
+
+            central_directory.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+            central_directory
+                .extend_from_slice(&[20, 0, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+            central_directory.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            central_directory.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            central_directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            central_directory.extend_from_slice(&[0; 12]);
+            central_directory.extend_from_slice(&offset.to_le_bytes());
+            central_directory.extend_from_slice(name.as_bytes());
+        }
+
+        let offset = archive.len() as u32;
+        archive.extend_from_slice(&central_directory);
+
+        archive.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+        archive.extend_from_slice(&[0, 0, 0, 0]);
+        archive.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        archive.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        archive.extend_from_slice(&(central_directory.len() as u32).to_le_bytes());
+        archive.extend_from_slice(&offset.to_le_bytes());
+        archive.extend_from_slice(&[0, 0]);
+
+        archive
+    }
+
+    #[test]
+    fn test_matrix_round_trip() {
+        let matrix: Matrix<2, 3> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.5]).unwrap();
+
+        let mut bytes = Vec::new();
+        matrix.to_npy_writer(&mut bytes).unwrap();
+
+        assert_eq!(&bytes[..6], MAGIC);
+        assert_eq!((bytes.len() - 6 * 8) % 64, 0);
+
+        let read: Matrix<2, 3> = Matrix::from_npy_reader(bytes.as_slice()).unwrap();
+
+        assert_eq!(read, matrix);
+    }
+
+    #[test]
+    fn test_vector_round_trip() {
+        let vector = VectorN::new([1.0, -2.0, 3.25]);
+
+        let mut bytes = Vec::new();
+        vector.to_npy_writer(&mut bytes).unwrap();
+
+        let header = String::from_utf8_lossy(&bytes[10..]);
+        assert!(header.contains("'shape': (3,)"));
+
+        let read: VectorN<3> = VectorN::from_npy_reader(bytes.as_slice()).unwrap();
+
+        assert_eq!(read, vector);
+    }
+
+    #[test]
+    fn test_big_endian_f4_fortran_v2() {
+        let header = "{'descr': '>f4', 'fortran_order': True, 'shape': (2, 3), }\n";
+
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&[2, 0]);
+        bytes.extend_from_slice(&(header.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+
+        // Column-major storage of [[1, 2, 3], [4, 5, 6]]
+        for entry in [1.0f32, 4.0, 2.0, 5.0, 3.0, 6.0] {
+            bytes.extend_from_slice(&entry.to_be_bytes());
+        }
+
+        let matrix: Matrix<2, 3> = Matrix::from_npy_reader(bytes.as_slice()).unwrap();
+
+        assert_eq!(matrix.data(), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    }
+
+    #[test]
+    fn test_shape_mismatch() {
+        let matrix: Matrix<2, 3> = Matrix::new(vec![0.0; 6]).unwrap();
+
+        let mut bytes = Vec::new();
+        matrix.to_npy_writer(&mut bytes).unwrap();
+
+        let result: Result<Matrix<3, 2>, _> = Matrix::from_npy_reader(bytes.as_slice());
+
+        assert!(matches!(
+            result,
+            Err(MatrixError::ShapeMismatch {
+                expected: (3, 2),
+                found: (2, 3)
+            })
+        ));
+    }
+
+    #[test]
+    fn test_hostile_shape_is_rejected_before_allocating() {
+        let mut bytes = Vec::new();
+        write_npy(
+            &mut bytes,
+            "(18446744073709551615, 4611686018427387904)",
+            &[],
+        )
+        .unwrap();
+
+        let result: Result<Matrix<2, 2>, _> = Matrix::from_npy_reader(bytes.as_slice());
+        assert!(matches!(result, Err(MatrixError::ShapeMismatch { .. })));
+
+        let result: Result<VectorN<4>, _> = VectorN::from_npy_reader(bytes.as_slice());
+        assert!(matches!(result, Err(MatrixError::ShapeMismatch { .. })));
+    }
+
+    #[test]
+    fn test_invalid_file() {
+        let result: Result<Matrix<1, 1>, _> =
+            Matrix::from_npy_reader(&b"PK\x03\x04 not an npy file"[..]);
+
+        assert!(matches!(result, Err(MatrixError::InvalidFormat(_))));
+    }
+
+    #[test]
+    fn test_hostile_zip64_offsets() {
+        // A zip64 locator followed by an end of central directory record that defers to it.
+        let end_records = |zip64_eocd: u64| {
+            let mut records = 0x0706_4b50u32.to_le_bytes().to_vec();
+            records.extend_from_slice(&[0; 4]);
+            records.extend_from_slice(&zip64_eocd.to_le_bytes());
+            records.extend_from_slice(&1u32.to_le_bytes());
+
+            records.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+            records.extend_from_slice(&[0, 0, 0, 0, 1, 0, 1, 0, 0, 0, 0, 0]);
+            records.extend_from_slice(&0xFFFF_FFFFu32.to_le_bytes());
+            records.extend_from_slice(&[0, 0]);
+            records
+        };
+
+        let archive = end_records(u64::MAX - 16);
+        let result: Result<Matrix<1, 1>, _> = Matrix::from_npz_reader(archive.as_slice(), "a");
+        assert!(matches!(result, Err(MatrixError::InvalidFormat(_))));
+
+        // A zip64 record at the start whose central directory offset is near the top of the range.
+        let mut archive = 0x0606_4b50u32.to_le_bytes().to_vec();
+        archive.resize(56, 0);
+        archive[32..40].copy_from_slice(&1u64.to_le_bytes());
+        archive[48..56].copy_from_slice(&(u64::MAX - 1).to_le_bytes());
+        archive.extend_from_slice(&end_records(0));
+
+        let result: Result<Matrix<1, 1>, _> = Matrix::from_npz_reader(archive.as_slice(), "a");
+        assert!(matches!(result, Err(MatrixError::InvalidFormat(_))));
+    }
+
+    #[test]
+    fn test_read_from_npz() {
+        let matrix: Matrix<2, 2> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+        let vector = VectorN::new([5.0, 6.0, 7.0]);
+
+        let mut matrix_bytes = Vec::new();
+        matrix.to_npy_writer(&mut matrix_bytes).unwrap();
+        let mut vector_bytes = Vec::new();
+        vector.to_npy_writer(&mut vector_bytes).unwrap();
+
+        let archive = stored_zip(&[("a.npy", matrix_bytes), ("b.npy", vector_bytes)]);
+
+        let a: Matrix<2, 2> = Matrix::from_npz_reader(archive.as_slice(), "a").unwrap();
+        let b: VectorN<3> = VectorN::from_npz_reader(archive.as_slice(), "b.npy").unwrap();
+
+        assert_eq!(a, matrix);
+        assert_eq!(b, vector);
+
+        let missing: Result<Matrix<2, 2>, _> = Matrix::from_npz_reader(archive.as_slice(), "c");
+        assert!(matches!(missing, Err(MatrixError::InvalidFormat(_))));
+    }
+}
@@ -0,0 +1,421 @@
+//! Conjugate Gradient, BiCGSTAB and GMRES.
+
+use crate::{
+    iterative::{
+        preconditioner::Preconditioner, to_vector, ConvergenceReport, LinearOperator, SolverOptions,
+    },
+    simd::{axpy, dot, norm},
+    vector::VectorN,
+};
+
+/// Applies an operator to a slice.
+fn apply<const N: usize>(operator: &impl LinearOperator<N>, x: &[f64]) -> Vec<f64> {
+    operator.mul_vector(&to_vector(x)).data().to_vec()
+}
+
+/// Applies the inverse of a preconditioner to a slice.
+fn precondition<const N: usize>(preconditioner: &impl Preconditioner<N>, r: &[f64]) -> Vec<f64> {
+    preconditioner.apply(&to_vector(r)).data().to_vec()
+}
+
+/// Solves `Ax = b` for a symmetric positive definite operator using the preconditioned Conjugate Gradient method.
+///
+/// The preconditioner has to be symmetric positive definite as well.
+pub fn conjugate_gradient<const N: usize>(
+    operator: &impl LinearOperator<N>,
+    b: &VectorN<N>,
+    preconditioner: &impl Preconditioner<N>,
+    options: &SolverOptions,
+) -> (VectorN<N>, ConvergenceReport) {
+    let target = options.tolerance * norm(b.data());
+
+    let mut x = vec![0.0; N];
+    let mut r = b.data().to_vec();
+    let mut z = precondition(preconditioner, &r);
+    let mut p = z.clone();
+    let mut rz = dot(&r, &z);
+
+    let mut report = ConvergenceReport {
+        converged: norm(&r) <= target,
+        iterations: 0,
+        residual_history: vec![norm(&r)],
+    };
+
+    while !report.converged && report.iterations < options.max_iterations {
+        let ap = apply(operator, &p);
+        let pap = dot(&p, &ap);
+
+        // The operator isn't positive definite (or p vanished), so there's nothing left to do.
+        if pap == 0.0 || !pap.is_finite() {
+            break;
+        }
+
+        let alpha = rz / pap;
+        axpy(alpha, &p, &mut x);
+        axpy(-alpha, &ap, &mut r);
+
+        let residual = norm(&r);
+        report.iterations += 1;
+        report.residual_history.push(residual);
+        report.converged = residual <= target;
+
+        z = precondition(preconditioner, &r);
+        let rz_next = dot(&r, &z);
+        let beta = rz_next / rz;
+        rz = rz_next;
+
+        p.iter_mut().zip(&z).for_each(|(p, z)| *p = z + beta * *p);
+    }
+
+    (to_vector(&x), report)
+}
+
+/// Solves `Ax = b` for a general operator using the right-preconditioned BiCGSTAB method.
+pub fn bicgstab<const N: usize>(
+    operator: &impl LinearOperator<N>,
+    b: &VectorN<N>,
+    preconditioner: &impl Preconditioner<N>,
+    options: &SolverOptions,
+) -> (VectorN<N>, ConvergenceReport) {
+    let target = options.tolerance * norm(b.data());
+
+    let mut x = vec![0.0; N];
+    let mut r = b.data().to_vec();
+    let r_hat = r.clone();
+    let mut p = vec![0.0; N];
+    let mut v = vec![0.0; N];
+    let (mut rho, mut alpha, mut omega) = (1.0, 1.0, 1.0);
+
+    let mut report = ConvergenceReport {
+        converged: norm(&r) <= target,
+        iterations: 0,
+        residual_history: vec![norm(&r)],
+    };
+
+    while !report.converged && report.iterations < options.max_iterations {
+        let rho_next = dot(&r_hat, &r);
+
+        // Breakdown, the method can't continue.
+        if rho_next == 0.0 || omega == 0.0 {
+            break;
+        }
+
+        let beta = (rho_next / rho) * (alpha / omega);
+        rho = rho_next;
+
+        for i in 0..N {
+            p[i] = r[i] + beta * (p[i] - omega * v[i]);
+        }
+
+        let y = precondition(preconditioner, &p);
+        v = apply(operator, &y);
+
+        let r_hat_v = dot(&r_hat, &v);
+        if r_hat_v == 0.0 {
+            break;
+        }
+        alpha = rho / r_hat_v;
+
+        axpy(alpha, &y, &mut x);
+        let mut s = r.clone();
+        axpy(-alpha, &v, &mut s);
+
+        report.iterations += 1;
+
+        // Exit early if the half step is already good enough.
+        let residual = norm(&s);
+        if residual <= target {
+            report.residual_history.push(residual);
+            report.converged = true;
+            break;
+        }
+
+        let z = precondition(preconditioner, &s);
+        let t = apply(operator, &z);
+
+        let tt = dot(&t, &t);
+        omega = if tt == 0.0 { 0.0 } else { dot(&t, &s) / tt };
+
+        axpy(omega, &z, &mut x);
+        r = s;
+        axpy(-omega, &t, &mut r);
+
+        let residual = norm(&r);
+        report.residual_history.push(residual);
+        report.converged = residual <= target;
+    }
+
+    (to_vector(&x), report)
+}
+
+/// Solves `Ax = b` for a general operator using the right-preconditioned, restarted GMRES method.
+///
+/// The Krylov subspace is rebuilt every `options.restart` iterations.
+pub fn gmres<const N: usize>(
+    operator: &impl LinearOperator<N>,
+    b: &VectorN<N>,
+    preconditioner: &impl Preconditioner<N>,
+    options: &SolverOptions,
+) -> (VectorN<N>, ConvergenceReport) {
+    let target = options.tolerance * norm(b.data());
+    let restart = options.restart.max(1);
+
+    let mut x = vec![0.0; N];
+
+    let mut report = ConvergenceReport {
+        converged: norm(b.data()) <= target,
+        iterations: 0,
+        residual_history: vec![norm(b.data())],
+    };
+
+    while !report.converged && report.iterations < options.max_iterations {
+        // r = b - Ax
+        let mut r = apply(operator, &x);
+        r.iter_mut().zip(b.data()).for_each(|(r, b)| *r = b - *r);
+        let beta = norm(&r);
+
+        if beta <= target {
+            report.converged = true;
+            break;
+        }
+
+        // Orthonormal basis of the Krylov subspace, the Hessenberg matrix (stored by column) and the
+        // Givens rotations that make it upper triangular.
+        let mut basis: Vec<Vec<f64>> = vec![r.iter().map(|r| r / beta).collect()];
+        let mut hessenberg: Vec<Vec<f64>> = Vec::new();
+        let mut rotations: Vec<(f64, f64)> = Vec::new();
+        let mut g = vec![beta];
+
+        while report.iterations < options.max_iterations && hessenberg.len() < restart {
+            let j = hessenberg.len();
+
+            let mut w = apply(operator, &precondition(preconditioner, &basis[j]));
+
+            // Modified Gram-Schmidt
+            let mut h: Vec<f64> = basis
+                .iter()
+                .map(|v| {
+                    let h = dot(&w, v);
+                    axpy(-h, v, &mut w);
+                    h
+                })
+                .collect();
+            let next = norm(&w);
+            h.push(next);
+
+            // Apply the previous rotations to the new column, then eliminate its last entry.
+            for (i, &(c, s)) in rotations.iter().enumerate() {
+                let (a, b) = (h[i], h[i + 1]);
+                h[i] = c * a + s * b;
+                h[i + 1] = -s * a + c * b;
+            }
+
+            let radius = h[j].hypot(h[j + 1]);
+            // A zero column keeps the residual in `g[j + 1]` rather than claiming it vanished.
+            let (c, s) = if radius == 0.0 {
+                (0.0, 1.0)
+            } else {
+                (h[j] / radius, h[j + 1] / radius)
+            };
+            h[j] = radius;
+            h[j + 1] = 0.0;
+            rotations.push((c, s));
+
+            g.push(-s * g[j]);
+            g[j] *= c;
+
+            hessenberg.push(h);
+
+            let residual = g[j + 1].abs();
+            report.iterations += 1;
+            report.residual_history.push(residual);
+
+            if residual <= target || next == 0.0 {
+                report.converged = residual <= target;
+                break;
+            }
+
+            basis.push(w.iter().map(|w| w / next).collect());
+        }
+
+        // Solve the triangular least squares problem and update the solution. A zero on the diagonal means
+        // the subspace stopped growing on a singular operator, so only the columns before it are used.
+        let k = hessenberg
+            .iter()
+            .enumerate()
+            .position(|(i, h)| h[i] == 0.0)
+            .unwrap_or(hessenberg.len());
+        let mut y = vec![0.0; k];
+        for i in (0..k).rev() {
+            let sum: f64 = ((i + 1)..k).map(|l| hessenberg[l][i] * y[l]).sum();
+            y[i] = (g[i] - sum) / hessenberg[i][i];
+        }
+
+        let mut update = vec![0.0; N];
+        y.iter()
+            .zip(&basis)
+            .for_each(|(&y, v)| axpy(y, v, &mut update));
+        axpy(1.0, &precondition(preconditioner, &update), &mut x);
+    }
+
+    (to_vector(&x), report)
+}
+
+#[cfg(test)]
+mod krylov_tests {
+    use super::*;
+    use crate::{
+        iterative::preconditioner::{Identity, Ilu0, Jacobi},
+        matrix::Matrix,
+        sparse::csr::CsrMatrix,
+    };
+
+    /// The 1-D Laplacian, which is symmetric positive definite.
+    fn laplacian<const N: usize>() -> Matrix<N, N> {
+        let data = (0..N * N)
+            .map(|i| match (i / N) as isize - (i % N) as isize {
+                0 => 2.0,
+                -1 | 1 => -1.0,
+                _ => 0.0,
+            })
+            .collect();
+
+        Matrix::new(data).unwrap()
+    }
+
+    /// A non-symmetric, diagonally dominant matrix.
+    fn convection<const N: usize>() -> Matrix<N, N> {
+        let data = (0..N * N)
+            .map(|i| match (i / N) as isize - (i % N) as isize {
+                0 => 4.0,
+                1 => -2.0,
+                -1 => -0.5,
+                -3 => 0.25,
+                _ => 0.0,
+            })
+            .collect();
+
+        Matrix::new(data).unwrap()
+    }
+
+    fn residual<const N: usize>(
+        operator: &impl LinearOperator<N>,
+        x: &VectorN<N>,
+        b: &VectorN<N>,
+    ) -> f64 {
+        let ax = operator.mul_vector(x);
+        let r: Vec<f64> = ax.data().iter().zip(b.data()).map(|(a, b)| b - a).collect();
+
+        norm(&r) / norm(b.data())
+    }
+
+    #[test]
+    fn test_conjugate_gradient() {
+        let matrix = laplacian::<20>();
+        let b = VectorN::new([1.0; 20]);
+        let options = SolverOptions::default();
+
+        let (x, report) = conjugate_gradient(&matrix, &b, &Identity, &options);
+
+        assert!(report.converged);
+        assert!(report.iterations <= 20);
+        assert_eq!(report.residual_history.len(), report.iterations + 1);
+        assert!(residual(&matrix, &x, &b) < 1e-9);
+
+        let csr = CsrMatrix::from_dense(&matrix);
+        let (x, report) = conjugate_gradient(&csr, &b, &Jacobi::new(&csr).unwrap(), &options);
+
+        assert!(report.converged);
+        assert!(residual(&csr, &x, &b) < 1e-9);
+    }
+
+    #[test]
+    fn test_bicgstab() {
+        let matrix = convection::<25>();
+        let b = VectorN::new([1.0; 25]);
+        let options = SolverOptions::default();
+
+        let (x, report) = bicgstab(&matrix, &b, &Identity, &options);
+
+        assert!(report.converged);
+        assert!(residual(&matrix, &x, &b) < 1e-9);
+
+        let ilu = Ilu0::from_dense(&matrix).unwrap();
+        let (x, preconditioned) = bicgstab(&matrix, &b, &ilu, &options);
+
+        assert!(preconditioned.converged);
+        assert!(preconditioned.iterations <= report.iterations);
+        assert!(residual(&matrix, &x, &b) < 1e-9);
+    }
+
+    #[test]
+    fn test_gmres() {
+        let matrix = convection::<25>();
+        let b = VectorN::new([1.0; 25]);
+
+        let options = SolverOptions {
+            restart: 5,
+            ..Default::default()
+        };
+
+        let (x, report) = gmres(&matrix, &b, &Identity, &options);
+
+        assert!(report.converged);
+        assert!(residual(&matrix, &x, &b) < 1e-9);
+
+        let csr = CsrMatrix::from_dense(&matrix);
+        let (x, report) = gmres(&csr, &b, &Ilu0::new(&csr).unwrap(), &options);
+
+        assert!(report.converged);
+        assert!(residual(&csr, &x, &b) < 1e-9);
+    }
+
+    #[test]
+    fn test_gmres_breakdown_on_singular_matrix() {
+        // `Ab` is zero, so the Krylov subspace stops growing before it contains a solution.
+        let matrix: Matrix<2, 2> = Matrix::new(vec![0.0, 1.0, 0.0, 0.0]).unwrap();
+        let b = VectorN::new([1.0, 0.0]);
+
+        let options = SolverOptions {
+            max_iterations: 5,
+            ..Default::default()
+        };
+
+        let (x, report) = gmres(&matrix, &b, &Identity, &options);
+
+        assert!(!report.converged);
+        assert_eq!(x.data(), &[0.0, 0.0]);
+        assert!(report.residual_history.iter().all(|&r| r == 1.0));
+    }
+
+    #[test]
+    fn test_iteration_limit() {
+        let matrix = laplacian::<50>();
+        let b = VectorN::new([1.0; 50]);
+
+        let options = SolverOptions {
+            max_iterations: 3,
+            ..Default::default()
+        };
+
+        let (_, report) = conjugate_gradient(&matrix, &b, &Identity, &options);
+        assert!(!report.converged);
+        assert_eq!(report.iterations, 3);
+
+        let (_, report) = gmres(&matrix, &b, &Identity, &options);
+        assert!(!report.converged);
+        assert_eq!(report.iterations, 3);
+    }
+
+    #[test]
+    fn test_zero_right_hand_side() {
+        let matrix = laplacian::<4>();
+        let b = VectorN::new([0.0; 4]);
+
+        let (x, report) = bicgstab(&matrix, &b, &Identity, &SolverOptions::default());
+
+        assert!(report.converged);
+        assert_eq!(report.iterations, 0);
+        assert_eq!(x.data(), &[0.0; 4]);
+    }
+}
@@ -0,0 +1,124 @@
+//! Iterative Krylov solvers for large (usually sparse) linear systems `Ax = b`.
+//!
+//! The solvers only need to be able to multiply by `A`, which is described by the [`LinearOperator`]
+//! trait. It is implemented for dense `Matrix<N, N>` as well as the sparse matrix types.
+
+pub mod krylov;
+pub mod preconditioner;
+
+use crate::{
+    matrix::Matrix,
+    simd,
+    sparse::{csc::CscMatrix, csr::CsrMatrix},
+    vector::VectorN,
+};
+
+/// A square linear operator that can be applied to vectors.
+pub trait LinearOperator<const N: usize> {
+    /// Returns the product of the operator and `x`.
+    fn mul_vector(&self, x: &VectorN<N>) -> VectorN<N>;
+
+    /// Returns the diagonal entries of the operator.
+    fn diagonal(&self) -> VectorN<N>;
+}
+
+impl<const N: usize> LinearOperator<N> for Matrix<N, N> {
+    fn mul_vector(&self, x: &VectorN<N>) -> VectorN<N> {
+        let data: Vec<f64> = self
+            .data()
+            .chunks(N)
+            .map(|row| simd::dot(row, x.data()))
+            .collect();
+
+        VectorN::new(data.try_into().unwrap())
+    }
+
+    fn diagonal(&self) -> VectorN<N> {
+        let data: Vec<f64> = (0..N).map(|i| self.data()[i * N + i]).collect();
+
+        VectorN::new(data.try_into().unwrap())
+    }
+}
+
+impl<const N: usize> LinearOperator<N> for CsrMatrix<N, N> {
+    fn mul_vector(&self, x: &VectorN<N>) -> VectorN<N> {
+        CsrMatrix::mul_vector(self, x)
+    }
+
+    fn diagonal(&self) -> VectorN<N> {
+        let data: Vec<f64> = (0..N).map(|i| self.get(i, i).unwrap()).collect();
+
+        VectorN::new(data.try_into().unwrap())
+    }
+}
+
+impl<const N: usize> LinearOperator<N> for CscMatrix<N, N> {
+    fn mul_vector(&self, x: &VectorN<N>) -> VectorN<N> {
+        CscMatrix::mul_vector(self, x)
+    }
+
+    fn diagonal(&self) -> VectorN<N> {
+        let data: Vec<f64> = (0..N).map(|i| self.get(i, i).unwrap()).collect();
+
+        VectorN::new(data.try_into().unwrap())
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Options controlling when an iterative solver stops.
+pub struct SolverOptions {
+    /// The solver stops once the residual norm `||b - Ax||` drops below `tolerance * ||b||`.
+    pub tolerance: f64,
+    /// The maximum number of iterations before giving up.
+    pub max_iterations: usize,
+    /// The number of iterations between restarts of GMRES.
+    pub restart: usize,
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: 1e-10,
+            max_iterations: 1000,
+            restart: 30,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A summary of how an iterative solver went.
+pub struct ConvergenceReport {
+    /// Whether the tolerance was reached.
+    pub converged: bool,
+    /// The number of iterations performed.
+    pub iterations: usize,
+    /// The residual norm before the first iteration and after every iteration.
+    pub residual_history: Vec<f64>,
+}
+
+fn to_vector<const N: usize>(data: &[f64]) -> VectorN<N> {
+    VectorN::new(data.try_into().unwrap())
+}
+
+#[cfg(test)]
+mod operator_tests {
+    use super::*;
+
+    #[test]
+    fn test_operators_agree() {
+        let matrix: Matrix<3, 3> =
+            Matrix::new(vec![4.0, 1.0, 0.0, 1.0, 3.0, 0.0, 0.0, 2.0, 5.0]).unwrap();
+        let csr = CsrMatrix::from_dense(&matrix);
+        let csc = CscMatrix::from_dense(&matrix);
+
+        let x = VectorN::new([1.0, 2.0, 3.0]);
+
+        assert_eq!(matrix.mul_vector(&x).data(), &[6.0, 7.0, 19.0]);
+        assert_eq!(csr.mul_vector(&x), matrix.mul_vector(&x));
+        assert_eq!(csc.mul_vector(&x), matrix.mul_vector(&x));
+
+        assert_eq!(matrix.diagonal().data(), &[4.0, 3.0, 5.0]);
+        assert_eq!(csr.diagonal(), matrix.diagonal());
+        assert_eq!(csc.diagonal(), matrix.diagonal());
+    }
+}
@@ -0,0 +1,213 @@
+//! Preconditioners for the iterative solvers.
+
+use crate::{
+    error::MatrixError,
+    iterative::{to_vector, LinearOperator},
+    matrix::Matrix,
+    sparse::csr::CsrMatrix,
+    vector::VectorN,
+};
+
+/// An approximation `M` of the operator `A` that is cheap to invert.
+pub trait Preconditioner<const N: usize> {
+    /// Returns `M^-1 r`.
+    fn apply(&self, r: &VectorN<N>) -> VectorN<N>;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+/// The identity preconditioner, i.e. no preconditioning at all.
+pub struct Identity;
+
+impl<const N: usize> Preconditioner<N> for Identity {
+    fn apply(&self, r: &VectorN<N>) -> VectorN<N> {
+        to_vector(r.data())
+    }
+}
+
+#[derive(Debug, Clone)]
+/// The Jacobi preconditioner, which scales by the inverse of the diagonal of `A`.
+pub struct Jacobi<const N: usize> {
+    inverse_diagonal: Vec<f64>,
+}
+
+impl<const N: usize> Jacobi<N> {
+    /// Creates a Jacobi preconditioner from the diagonal of an operator.
+    pub fn new(operator: &impl LinearOperator<N>) -> Result<Self, MatrixError> {
+        let diagonal = operator.diagonal();
+
+        if diagonal.data().contains(&0.0) {
+            return Err(MatrixError::Singular);
+        }
+
+        Ok(Self {
+            inverse_diagonal: diagonal.data().iter().map(|f| 1.0 / f).collect(),
+        })
+    }
+}
+
+impl<const N: usize> Preconditioner<N> for Jacobi<N> {
+    fn apply(&self, r: &VectorN<N>) -> VectorN<N> {
+        let data: Vec<f64> = r
+            .data()
+            .iter()
+            .zip(&self.inverse_diagonal)
+            .map(|(r, d)| r * d)
+            .collect();
+
+        to_vector(&data)
+    }
+}
+
+#[derive(Debug, Clone)]
+/// The incomplete LU factorization with zero fill-in, ILU(0).
+///
+/// `L` and `U` are stored together in the sparsity pattern of `A`, with the unit diagonal of `L` left implicit.
+pub struct Ilu0<const N: usize> {
+    offsets: Vec<usize>,
+    columns: Vec<usize>,
+    values: Vec<f64>,
+    diagonal: Vec<usize>,
+}
+
+impl<const N: usize> Ilu0<N> {
+    /// Computes the ILU(0) factorization of a sparse matrix.
+    ///
+    /// Every diagonal entry has to be stored, and every pivot has to be non-zero.
+    pub fn new(matrix: &CsrMatrix<N, N>) -> Result<Self, MatrixError> {
+        let mut offsets = vec![0];
+        let mut columns = Vec::with_capacity(matrix.nnz());
+        let mut values = Vec::with_capacity(matrix.nnz());
+
+        for i in 0..N {
+            for (j, f) in matrix.row(i).unwrap() {
+                columns.push(j);
+                values.push(f);
+            }
+            offsets.push(columns.len());
+        }
+
+        let diagonal = (0..N)
+            .map(|i| {
+                (offsets[i]..offsets[i + 1])
+                    .find(|&k| columns[k] == i)
+                    .ok_or(MatrixError::Singular)
+            })
+            .collect::<Result<Vec<usize>, MatrixError>>()?;
+
+        // IKJ variant of Gaussian elimination, restricted to the existing sparsity pattern.
+        for i in 0..N {
+            for k in offsets[i]..diagonal[i] {
+                let pivot_row = columns[k];
+                let pivot = values[diagonal[pivot_row]];
+
+                if pivot == 0.0 {
+                    return Err(MatrixError::Singular);
+                }
+
+                values[k] /= pivot;
+                let factor = values[k];
+
+                for j in (k + 1)..offsets[i + 1] {
+                    let column = columns[j];
+
+                    if let Ok(p) =
+                        columns[diagonal[pivot_row]..offsets[pivot_row + 1]].binary_search(&column)
+                    {
+                        values[j] -= factor * values[diagonal[pivot_row] + p];
+                    }
+                }
+            }
+
+            if values[diagonal[i]] == 0.0 {
+                return Err(MatrixError::Singular);
+            }
+        }
+
+        Ok(Self {
+            offsets,
+            columns,
+            values,
+            diagonal,
+        })
+    }
+
+    /// Computes the ILU(0) factorization of a dense matrix, using its non-zero entries as the sparsity pattern.
+    pub fn from_dense(matrix: &Matrix<N, N>) -> Result<Self, MatrixError> {
+        Self::new(&CsrMatrix::from_dense(matrix))
+    }
+}
+
+impl<const N: usize> Preconditioner<N> for Ilu0<N> {
+    fn apply(&self, r: &VectorN<N>) -> VectorN<N> {
+        let mut x = r.data().to_vec();
+
+        // Forward substitution with the unit lower triangle.
+        for i in 0..N {
+            for k in self.offsets[i]..self.diagonal[i] {
+                x[i] -= self.values[k] * x[self.columns[k]];
+            }
+        }
+
+        // Back substitution with the upper triangle.
+        for i in (0..N).rev() {
+            for k in (self.diagonal[i] + 1)..self.offsets[i + 1] {
+                x[i] -= self.values[k] * x[self.columns[k]];
+            }
+            x[i] /= self.values[self.diagonal[i]];
+        }
+
+        to_vector(&x)
+    }
+}
+
+#[cfg(test)]
+mod preconditioner_tests {
+    use super::*;
+
+    #[test]
+    fn test_jacobi() {
+        let matrix: Matrix<2, 2> = Matrix::new(vec![2.0, 1.0, 1.0, 4.0]).unwrap();
+
+        let jacobi = Jacobi::new(&matrix).unwrap();
+
+        assert_eq!(jacobi.apply(&VectorN::new([1.0, 1.0])).data(), &[0.5, 0.25]);
+
+        let singular: Matrix<2, 2> = Matrix::new(vec![0.0, 1.0, 1.0, 4.0]).unwrap();
+        assert!(Jacobi::new(&singular).is_err());
+    }
+
+    #[test]
+    fn test_ilu0_is_exact_for_tridiagonal_matrices() {
+        // ILU(0) of a tridiagonal matrix has no dropped fill-in, so it is the exact LU factorization.
+        #[rustfmt::skip]
+        let data = vec![
+            4.0, -1.0, 0.0, 0.0,
+            -1.0, 4.0, -1.0, 0.0,
+            0.0, -1.0, 4.0, -1.0,
+            0.0, 0.0, -1.0, 4.0,
+        ];
+        let matrix: Matrix<4, 4> = Matrix::new(data).unwrap();
+
+        let ilu = Ilu0::from_dense(&matrix).unwrap();
+
+        let b = VectorN::new([1.0, 2.0, 3.0, 4.0]);
+        let x = ilu.apply(&b);
+
+        let residual: f64 = matrix
+            .mul_vector(&x)
+            .data()
+            .iter()
+            .zip(b.data())
+            .map(|(a, b)| (a - b).abs())
+            .sum();
+
+        assert!(residual < 1e-12);
+    }
+
+    #[test]
+    fn test_ilu0_requires_diagonal() {
+        let matrix: Matrix<2, 2> = Matrix::new(vec![0.0, 1.0, 1.0, 0.0]).unwrap();
+
+        assert!(Ilu0::from_dense(&matrix).is_err());
+    }
+}
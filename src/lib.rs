@@ -1,4 +1,9 @@
 //! A really bad toy matrix library just so i can practice some rust. Don't use this. Use [Nalgebra](https://nalgebra.org/).
 pub mod error;
+pub mod io;
+pub mod iterative;
 pub mod matrix;
-pub mod vector;
\ No newline at end of file
+pub mod parallel;
+mod simd;
+pub mod sparse;
+pub mod vector;
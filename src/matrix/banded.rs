@@ -0,0 +1,412 @@
+//! Tridiagonal and banded matrices, which only store the diagonals around the main one.
+//!
+//! Neither solver pivots, so they are meant for the diagonally dominant or symmetric positive definite
+//! systems that discretized differential equations and splines produce. A `Singular` error is returned
+//! if a zero pivot turns up.
+
+use crate::{error::MatrixError, matrix::Matrix, vector::VectorN};
+
+#[derive(Debug, Clone, PartialEq)]
+/// An `N` x `N` matrix that is zero outside the main diagonal and the diagonals right above and below
+/// it.
+pub struct Tridiagonal<const N: usize> {
+    lower: Vec<f64>,
+    diagonal: Vec<f64>,
+    upper: Vec<f64>,
+}
+
+impl<const N: usize> Tridiagonal<N> {
+    /// Creates a tridiagonal matrix from its subdiagonal, diagonal and superdiagonal, which have to have
+    /// `N - 1`, `N` and `N - 1` entries.
+    pub fn new(lower: Vec<f64>, diagonal: Vec<f64>, upper: Vec<f64>) -> Result<Self, MatrixError> {
+        let off_diagonal = N.saturating_sub(1);
+
+        if diagonal.len() != N || lower.len() != off_diagonal || upper.len() != off_diagonal {
+            return Err(MatrixError::InvalidDimensions);
+        }
+
+        Ok(Self {
+            lower,
+            diagonal,
+            upper,
+        })
+    }
+
+    /// Takes the three middle diagonals of `matrix` and drops the rest.
+    pub fn from_dense(matrix: &Matrix<N, N>) -> Self {
+        let data = &matrix.matrix;
+
+        Self {
+            lower: (1..N).map(|i| data[i * N + i - 1]).collect(),
+            diagonal: (0..N).map(|i| data[i * N + i]).collect(),
+            upper: (1..N).map(|i| data[(i - 1) * N + i]).collect(),
+        }
+    }
+
+    /// Returns the subdiagonal, where entry `i` is at row `i + 1` and column `i`.
+    pub fn lower(&self) -> &[f64] {
+        &self.lower
+    }
+
+    /// Returns the main diagonal.
+    pub fn diagonal(&self) -> &[f64] {
+        &self.diagonal
+    }
+
+    /// Returns the superdiagonal, where entry `i` is at row `i` and column `i + 1`.
+    pub fn upper(&self) -> &[f64] {
+        &self.upper
+    }
+
+    /// Returns the matrix as a plain dense matrix.
+    pub fn to_dense(&self) -> Matrix<N, N> {
+        let mut matrix = vec![0.0; N * N];
+
+        for i in 0..N {
+            matrix[i * N + i] = self.diagonal[i];
+
+            if i > 0 {
+                matrix[i * N + i - 1] = self.lower[i - 1];
+                matrix[(i - 1) * N + i] = self.upper[i - 1];
+            }
+        }
+
+        Matrix { matrix }
+    }
+
+    /// Returns the product of the matrix and `x`.
+    pub fn mul_vector(&self, x: &VectorN<N>) -> VectorN<N> {
+        let x = x.data();
+
+        VectorN::new(std::array::from_fn(|i| {
+            let mut sum = self.diagonal[i] * x[i];
+
+            if i > 0 {
+                sum += self.lower[i - 1] * x[i - 1];
+            }
+            if i + 1 < N {
+                sum += self.upper[i] * x[i + 1];
+            }
+
+            sum
+        }))
+    }
+
+    /// Solves `Ax = b` in linear time with the Thomas algorithm.
+    pub fn solve(&self, b: &VectorN<N>) -> Result<VectorN<N>, MatrixError> {
+        let b = b.data();
+        let mut upper = vec![0.0; N];
+        let mut x = [0.0; N];
+
+        // Eliminate the subdiagonal, scaling each row so its diagonal entry becomes one.
+        for i in 0..N {
+            let (previous_upper, previous_x) = match i {
+                0 => (0.0, 0.0),
+                _ => (upper[i - 1], x[i - 1]),
+            };
+            let lower = if i > 0 { self.lower[i - 1] } else { 0.0 };
+
+            let pivot = self.diagonal[i] - lower * previous_upper;
+            if pivot == 0.0 {
+                return Err(MatrixError::Singular);
+            }
+
+            if i + 1 < N {
+                upper[i] = self.upper[i] / pivot;
+            }
+            x[i] = (b[i] - lower * previous_x) / pivot;
+        }
+
+        for i in (0..N.saturating_sub(1)).rev() {
+            x[i] -= upper[i] * x[i + 1];
+        }
+
+        Ok(VectorN::new(x))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// An `N` x `N` matrix that is zero more than `lower_bandwidth` diagonals below or `upper_bandwidth`
+/// diagonals above the main diagonal.
+pub struct Banded<const N: usize> {
+    lower_bandwidth: usize,
+    upper_bandwidth: usize,
+    /// Row `i` holds the entries from column `i - lower_bandwidth` to `i + upper_bandwidth`.
+    data: Vec<f64>,
+}
+
+impl<const N: usize> Banded<N> {
+    /// Creates a banded matrix of zeros with the given bandwidths.
+    pub fn new(lower_bandwidth: usize, upper_bandwidth: usize) -> Self {
+        Self {
+            lower_bandwidth,
+            upper_bandwidth,
+            data: vec![0.0; N * (lower_bandwidth + upper_bandwidth + 1)],
+        }
+    }
+
+    /// Takes the band of `matrix` with the given bandwidths and drops the rest.
+    pub fn from_dense(
+        matrix: &Matrix<N, N>,
+        lower_bandwidth: usize,
+        upper_bandwidth: usize,
+    ) -> Self {
+        let mut banded = Self::new(lower_bandwidth, upper_bandwidth);
+
+        for i in 0..N {
+            for j in banded.columns(i) {
+                let index = banded.index(i, j).unwrap();
+                banded.data[index] = matrix.matrix[i * N + j];
+            }
+        }
+
+        banded
+    }
+
+    /// Returns the number of nonzero diagonals below the main diagonal.
+    pub fn lower_bandwidth(&self) -> usize {
+        self.lower_bandwidth
+    }
+
+    /// Returns the number of nonzero diagonals above the main diagonal.
+    pub fn upper_bandwidth(&self) -> usize {
+        self.upper_bandwidth
+    }
+
+    /// Returns the entry at row `i` and column `j`, which is zero outside the band.
+    pub fn get(&self, i: usize, j: usize) -> Option<f64> {
+        if i >= N || j >= N {
+            return None;
+        }
+
+        Some(self.index(i, j).map_or(0.0, |index| self.data[index]))
+    }
+
+    /// Sets the entry at row `i` and column `j`, returning an `UndefinedIndex` error if it is outside
+    /// the matrix or the band.
+    pub fn set(&mut self, entry: f64, i: usize, j: usize) -> Result<(), MatrixError> {
+        let index = self.index(i, j).ok_or(MatrixError::UndefinedIndex)?;
+        self.data[index] = entry;
+
+        Ok(())
+    }
+
+    /// Returns the matrix as a plain dense matrix.
+    pub fn to_dense(&self) -> Matrix<N, N> {
+        let mut matrix = vec![0.0; N * N];
+
+        for i in 0..N {
+            for j in self.columns(i) {
+                matrix[i * N + j] = self.data[self.index(i, j).unwrap()];
+            }
+        }
+
+        Matrix { matrix }
+    }
+
+    /// Returns the product of the matrix and `x`.
+    pub fn mul_vector(&self, x: &VectorN<N>) -> VectorN<N> {
+        let x = x.data();
+
+        VectorN::new(std::array::from_fn(|i| {
+            self.columns(i)
+                .map(|j| self.data[self.index(i, j).unwrap()] * x[j])
+                .sum()
+        }))
+    }
+
+    /// Factors the matrix into `LU` without pivoting, keeping the band structure: `L` has the lower
+    /// bandwidth and `U` the upper bandwidth of the matrix.
+    pub fn lu(&self) -> Result<BandedLu<N>, MatrixError> {
+        let mut factors = self.clone();
+        let (p, q) = (self.lower_bandwidth, self.upper_bandwidth);
+
+        for k in 0..N {
+            let pivot = factors.data[factors.index(k, k).unwrap()];
+            if pivot == 0.0 {
+                return Err(MatrixError::Singular);
+            }
+
+            for i in k + 1..N.min(k + p + 1) {
+                let index = factors.index(i, k).unwrap();
+                let multiplier = factors.data[index] / pivot;
+                factors.data[index] = multiplier;
+
+                for j in k + 1..N.min(k + q + 1) {
+                    let source = factors.data[factors.index(k, j).unwrap()];
+                    let target = factors.index(i, j).unwrap();
+                    factors.data[target] -= multiplier * source;
+                }
+            }
+        }
+
+        Ok(BandedLu { factors })
+    }
+
+    /// Solves `Ax = b` with a banded LU decomposition.
+    pub fn solve(&self, b: &VectorN<N>) -> Result<VectorN<N>, MatrixError> {
+        Ok(self.lu()?.solve(b))
+    }
+
+    /// The columns of row `i` inside the band.
+    fn columns(&self, i: usize) -> std::ops::Range<usize> {
+        i.saturating_sub(self.lower_bandwidth)..N.min(i + self.upper_bandwidth + 1)
+    }
+
+    /// The position of entry `(i, j)` in the band storage, if it is inside the band.
+    fn index(&self, i: usize, j: usize) -> Option<usize> {
+        if i >= N || j >= N || j + self.lower_bandwidth < i || j > i + self.upper_bandwidth {
+            return None;
+        }
+
+        let width = self.lower_bandwidth + self.upper_bandwidth + 1;
+
+        Some(i * width + j + self.lower_bandwidth - i)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The LU decomposition of a banded matrix, with the unit lower triangular `L` and upper triangular `U`
+/// stored together in the band.
+pub struct BandedLu<const N: usize> {
+    factors: Banded<N>,
+}
+
+impl<const N: usize> BandedLu<N> {
+    /// Solves `LUx = b` by forward and back substitution.
+    pub fn solve(&self, b: &VectorN<N>) -> VectorN<N> {
+        let factors = &self.factors;
+        let entry = |i, j| factors.data[factors.index(i, j).unwrap()];
+        let mut x: [f64; N] = b.data().try_into().unwrap();
+
+        for i in 0..N {
+            for k in i.saturating_sub(factors.lower_bandwidth)..i {
+                x[i] -= entry(i, k) * x[k];
+            }
+        }
+
+        for i in (0..N).rev() {
+            for j in i + 1..N.min(i + factors.upper_bandwidth + 1) {
+                x[i] -= entry(i, j) * x[j];
+            }
+            x[i] /= entry(i, i);
+        }
+
+        VectorN::new(x)
+    }
+
+    /// Returns the determinant of the factored matrix, the product of the diagonal of `U`.
+    pub fn determinant(&self) -> f64 {
+        (0..N)
+            .map(|i| self.factors.data[self.factors.index(i, i).unwrap()])
+            .product()
+    }
+}
+
+#[cfg(test)]
+mod banded_tests {
+    use super::*;
+
+    /// The second difference matrix of the 1-D Poisson equation.
+    fn poisson<const N: usize>() -> Tridiagonal<N> {
+        Tridiagonal::new(vec![-1.0; N - 1], vec![2.0; N], vec![-1.0; N - 1]).unwrap()
+    }
+
+    #[test]
+    fn test_tridiagonal_storage() {
+        let matrix = poisson::<3>();
+
+        assert_eq!(
+            matrix.to_dense().data(),
+            &[2.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0]
+        );
+        assert_eq!(Tridiagonal::from_dense(&matrix.to_dense()), matrix);
+        assert!(matches!(
+            Tridiagonal::<3>::new(vec![1.0], vec![1.0; 3], vec![1.0; 2]),
+            Err(MatrixError::InvalidDimensions)
+        ));
+
+        let x = VectorN::new([1.0, 2.0, 3.0]);
+        assert_eq!(matrix.mul_vector(&x).data(), &[0.0, 0.0, 4.0]);
+    }
+
+    #[test]
+    fn test_thomas_algorithm() {
+        let matrix = poisson::<5>();
+        let b = VectorN::new([1.0, 0.0, 2.0, -1.0, 3.0]);
+
+        let x = matrix.solve(&b).unwrap();
+        for (a, b) in matrix.mul_vector(&x).data().iter().zip(b.data()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+
+        let singular: Tridiagonal<2> =
+            Tridiagonal::new(vec![1.0], vec![1.0, 1.0], vec![1.0]).unwrap();
+        assert!(matches!(
+            singular.solve(&VectorN::new([1.0, 1.0])),
+            Err(MatrixError::Singular)
+        ));
+    }
+
+    #[test]
+    fn test_banded_storage() {
+        let dense: Matrix<4, 4> = Matrix::new(vec![
+            4.0, 1.0, 2.0, 0.0, //
+            1.0, 5.0, 1.0, 2.0, //
+            0.0, 1.0, 6.0, 1.0, //
+            0.0, 0.0, 1.0, 7.0,
+        ])
+        .unwrap();
+
+        let mut banded = Banded::from_dense(&dense, 1, 2);
+        assert_eq!(banded.to_dense(), dense);
+        assert_eq!(banded.get(3, 0), Some(0.0));
+        assert_eq!(banded.get(4, 0), None);
+
+        assert!(matches!(
+            banded.set(1.0, 3, 0),
+            Err(MatrixError::UndefinedIndex)
+        ));
+        banded.set(3.0, 0, 2).unwrap();
+        assert_eq!(banded.get(0, 2), Some(3.0));
+
+        let x = VectorN::new([1.0, -1.0, 2.0, 0.5]);
+        let expected: Vec<f64> = banded
+            .to_dense()
+            .data()
+            .chunks(4)
+            .map(|row| row.iter().zip(x.data()).map(|(a, b)| a * b).sum())
+            .collect();
+        assert_eq!(banded.mul_vector(&x).data(), &expected[..]);
+    }
+
+    #[test]
+    fn test_banded_lu() {
+        let dense: Matrix<5, 5> = Matrix::new(vec![
+            4.0, 1.0, 0.5, 0.0, 0.0, //
+            1.0, 5.0, 1.0, 0.5, 0.0, //
+            0.0, 1.0, 6.0, 1.0, 0.5, //
+            0.0, 0.0, 1.0, 7.0, 1.0, //
+            0.0, 0.0, 0.0, 1.0, 8.0,
+        ])
+        .unwrap();
+        let banded = Banded::from_dense(&dense, 1, 2);
+        let b = VectorN::new([1.0, 2.0, 3.0, 4.0, 5.0]);
+
+        let x = banded.solve(&b).unwrap();
+        for (a, b) in banded.mul_vector(&x).data().iter().zip(b.data()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+
+        let determinant = banded.lu().unwrap().determinant();
+        assert!((determinant - dense.determinant()).abs() < 1e-9);
+
+        // The tridiagonal solver and a banded one with bandwidths of one agree.
+        let poisson = poisson::<5>();
+        let banded = Banded::from_dense(&poisson.to_dense(), 1, 1);
+        let (x, y) = (poisson.solve(&b).unwrap(), banded.solve(&b).unwrap());
+        for (x, y) in x.data().iter().zip(y.data()) {
+            assert!((x - y).abs() < 1e-12);
+        }
+    }
+}
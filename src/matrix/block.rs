@@ -0,0 +1,191 @@
+//! Concatenating matrices into block matrices and splitting them apart again.
+//!
+//! Const generics can't add dimensions on stable Rust, so the output sizes are given as extra const
+//! parameters (usually inferred from the binding) and checked at runtime, returning a `ShapeMismatch`
+//! error if they don't add up.
+
+use crate::{error::MatrixError, matrix::Matrix, vector::VectorN};
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    /// Places `other` to the right of this matrix, giving an `R` x `C3` matrix where `C3 = C + C2`.
+    pub fn hstack<const C2: usize, const C3: usize>(
+        &self,
+        other: &Matrix<R, C2>,
+    ) -> Result<Matrix<R, C3>, MatrixError> {
+        check_shape((R, C + C2), (R, C3))?;
+
+        let matrix = (0..R)
+            .flat_map(|i| {
+                self.matrix[i * C..(i + 1) * C]
+                    .iter()
+                    .chain(&other.matrix[i * C2..(i + 1) * C2])
+            })
+            .copied()
+            .collect();
+
+        Ok(Matrix { matrix })
+    }
+
+    /// Places `other` below this matrix, giving an `R3` x `C` matrix where `R3 = R + R2`.
+    pub fn vstack<const R2: usize, const R3: usize>(
+        &self,
+        other: &Matrix<R2, C>,
+    ) -> Result<Matrix<R3, C>, MatrixError> {
+        check_shape((R + R2, C), (R3, C))?;
+
+        let matrix = self.matrix.iter().chain(&other.matrix).copied().collect();
+
+        Ok(Matrix { matrix })
+    }
+
+    /// Appends `b` as an extra column, giving the augmented matrix `[A | b]` with `C2 = C + 1` columns.
+    pub fn augment<const C2: usize>(&self, b: &VectorN<R>) -> Result<Matrix<R, C2>, MatrixError> {
+        let column: Matrix<R, 1> = Matrix {
+            matrix: b.data().to_vec(),
+        };
+
+        self.hstack(&column)
+    }
+
+    /// Assembles the 2x2 block matrix `[[a11, a12], [a21, a22]]`, where `R = R1 + R2` and `C = C1 + C2`.
+    pub fn from_blocks<const R1: usize, const R2: usize, const C1: usize, const C2: usize>(
+        a11: &Matrix<R1, C1>,
+        a12: &Matrix<R1, C2>,
+        a21: &Matrix<R2, C1>,
+        a22: &Matrix<R2, C2>,
+    ) -> Result<Matrix<R, C>, MatrixError> {
+        check_shape((R1 + R2, C1 + C2), (R, C))?;
+
+        let top = rows(&a11.matrix, R1, C1).zip(rows(&a12.matrix, R1, C2));
+        let bottom = rows(&a21.matrix, R2, C1).zip(rows(&a22.matrix, R2, C2));
+
+        let matrix = top
+            .chain(bottom)
+            .flat_map(|(left, right)| left.iter().chain(right))
+            .copied()
+            .collect();
+
+        Ok(Matrix { matrix })
+    }
+
+    /// Splits the matrix into its first `R1` rows and the remaining `R2` rows, where `R = R1 + R2`.
+    pub fn split_at_row<const R1: usize, const R2: usize>(
+        &self,
+    ) -> Result<(Matrix<R1, C>, Matrix<R2, C>), MatrixError> {
+        check_shape((R, C), (R1 + R2, C))?;
+
+        let (top, bottom) = self.matrix.split_at(R1 * C);
+
+        Ok((
+            Matrix {
+                matrix: top.to_vec(),
+            },
+            Matrix {
+                matrix: bottom.to_vec(),
+            },
+        ))
+    }
+
+    /// Splits the matrix into its first `C1` columns and the remaining `C2` columns, where `C = C1 + C2`.
+    pub fn split_at_col<const C1: usize, const C2: usize>(
+        &self,
+    ) -> Result<(Matrix<R, C1>, Matrix<R, C2>), MatrixError> {
+        check_shape((R, C), (R, C1 + C2))?;
+
+        let (left, right): (Vec<_>, Vec<_>) =
+            rows(&self.matrix, R, C).map(|row| row.split_at(C1)).unzip();
+
+        Ok((
+            Matrix {
+                matrix: left.concat(),
+            },
+            Matrix {
+                matrix: right.concat(),
+            },
+        ))
+    }
+}
+
+fn check_shape(expected: (usize, usize), found: (usize, usize)) -> Result<(), MatrixError> {
+    if expected != found {
+        return Err(MatrixError::ShapeMismatch { expected, found });
+    }
+
+    Ok(())
+}
+
+/// Iterates over the rows of a row-major matrix, which also works if it has no columns.
+fn rows(data: &[f64], rows: usize, columns: usize) -> impl Iterator<Item = &[f64]> {
+    (0..rows).map(move |i| &data[i * columns..(i + 1) * columns])
+}
+
+#[cfg(test)]
+mod block_tests {
+    use super::*;
+
+    #[test]
+    fn test_stacking() {
+        let a: Matrix<2, 2> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+        let b: Matrix<2, 1> = Matrix::new(vec![5.0, 6.0]).unwrap();
+        let c: Matrix<1, 2> = Matrix::new(vec![7.0, 8.0]).unwrap();
+
+        let wide: Matrix<2, 3> = a.hstack(&b).unwrap();
+        assert_eq!(wide.data(), &[1.0, 2.0, 5.0, 3.0, 4.0, 6.0]);
+
+        let tall: Matrix<3, 2> = a.vstack(&c).unwrap();
+        assert_eq!(tall.data(), &[1.0, 2.0, 3.0, 4.0, 7.0, 8.0]);
+
+        let wrong: Result<Matrix<2, 4>, _> = a.hstack(&b);
+        assert!(matches!(
+            wrong,
+            Err(MatrixError::ShapeMismatch {
+                expected: (2, 3),
+                found: (2, 4)
+            })
+        ));
+    }
+
+    #[test]
+    fn test_augment() {
+        let a: Matrix<2, 2> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+        let b = VectorN::new([5.0, 6.0]);
+
+        let augmented: Matrix<2, 3> = a.augment(&b).unwrap();
+
+        assert_eq!(augmented.data(), &[1.0, 2.0, 5.0, 3.0, 4.0, 6.0]);
+    }
+
+    #[test]
+    fn test_from_blocks() {
+        // A KKT system [[H, Aᵀ], [A, 0]].
+        let h: Matrix<2, 2> = Matrix::new(vec![2.0, 0.0, 0.0, 2.0]).unwrap();
+        let a_t: Matrix<2, 1> = Matrix::new(vec![1.0, 1.0]).unwrap();
+        let a: Matrix<1, 2> = Matrix::new(vec![1.0, 1.0]).unwrap();
+        let zero: Matrix<1, 1> = Matrix::new(vec![0.0]).unwrap();
+
+        let kkt: Matrix<3, 3> = Matrix::from_blocks(&h, &a_t, &a, &zero).unwrap();
+
+        assert_eq!(kkt.data(), &[2.0, 0.0, 1.0, 0.0, 2.0, 1.0, 1.0, 1.0, 0.0]);
+        assert!(Matrix::<3, 4>::from_blocks(&h, &a_t, &a, &zero).is_err());
+    }
+
+    #[test]
+    fn test_splitting() {
+        let matrix: Matrix<3, 3> =
+            Matrix::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]).unwrap();
+
+        let (top, bottom): (Matrix<1, 3>, Matrix<2, 3>) = matrix.split_at_row().unwrap();
+        assert_eq!(top.data(), &[1.0, 2.0, 3.0]);
+        assert_eq!(bottom.data(), &[4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+
+        let (left, right): (Matrix<3, 2>, Matrix<3, 1>) = matrix.split_at_col().unwrap();
+        assert_eq!(left.data(), &[1.0, 2.0, 4.0, 5.0, 7.0, 8.0]);
+        assert_eq!(right.data(), &[3.0, 6.0, 9.0]);
+
+        // Splitting and stacking again gives back the original matrix.
+        let joined: Matrix<3, 3> = left.hstack(&right).unwrap();
+        assert_eq!(joined, matrix);
+
+        assert!(matrix.split_at_row::<1, 1>().is_err());
+    }
+}
@@ -0,0 +1,219 @@
+//! Traces, determinants, minors, cofactors and adjugates of square matrices.
+//!
+//! Determinants are computed with the fraction-free Bareiss algorithm with partial pivoting, so every
+//! division is exact and matrices of small integers give exact integer results. Cofactors and adjugates
+//! are built from these determinants, so they are exact as well.
+
+use crate::{error::MatrixError, matrix::Matrix};
+
+impl<const N: usize> Matrix<N, N> {
+    /// Returns the sum of the diagonal entries.
+    pub fn trace(&self) -> f64 {
+        (0..N).map(|i| self.matrix[i * N + i]).sum()
+    }
+
+    /// Returns the determinant of the matrix.
+    pub fn determinant(&self) -> f64 {
+        determinant(N, self.matrix.clone())
+    }
+
+    /// Returns the `M` x `M` matrix left after removing row `i` and column `j`.
+    ///
+    /// `M` has to be `N - 1`, otherwise a `ShapeMismatch` error is returned.
+    pub fn submatrix<const M: usize>(
+        &self,
+        i: usize,
+        j: usize,
+    ) -> Result<Matrix<M, M>, MatrixError> {
+        if M + 1 != N {
+            return Err(MatrixError::ShapeMismatch {
+                expected: (N.saturating_sub(1), N.saturating_sub(1)),
+                found: (M, M),
+            });
+        }
+
+        let matrix = self.remove(i, j)?;
+
+        Ok(Matrix { matrix })
+    }
+
+    /// Returns the minor `M_ij`, the determinant of the matrix left after removing row `i` and column `j`.
+    pub fn minor(&self, i: usize, j: usize) -> Result<f64, MatrixError> {
+        Ok(determinant(N - 1, self.remove(i, j)?))
+    }
+
+    /// Returns the cofactor `C_ij = (-1)^(i + j) M_ij`.
+    pub fn cofactor(&self, i: usize, j: usize) -> Result<f64, MatrixError> {
+        let minor = self.minor(i, j)?;
+        let sign = if (i + j) & 1 == 0 { 1.0 } else { -1.0 };
+
+        Ok(sign * minor)
+    }
+
+    /// Returns the matrix of all cofactors.
+    pub fn cofactor_matrix(&self) -> Matrix<N, N> {
+        let matrix = (0..N * N)
+            .map(|index| self.cofactor(index / N, index % N).unwrap())
+            .collect();
+
+        Matrix { matrix }
+    }
+
+    /// Returns the adjugate, the transpose of the cofactor matrix, which satisfies `A adj(A) = det(A) I`.
+    pub fn adjugate(&self) -> Matrix<N, N> {
+        self.cofactor_matrix().transpose()
+    }
+
+    /// Returns the entries left after removing row `i` and column `j`, in row-major order.
+    fn remove(&self, i: usize, j: usize) -> Result<Vec<f64>, MatrixError> {
+        if i >= N || j >= N {
+            return Err(MatrixError::UndefinedIndex);
+        }
+
+        Ok(self
+            .matrix
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| index / N != i && index % N != j)
+            .map(|(_, f)| *f)
+            .collect())
+    }
+}
+
+/// Returns the determinant of an `n` x `n` row-major matrix with the Bareiss algorithm.
+fn determinant(n: usize, mut a: Vec<f64>) -> f64 {
+    let mut sign = 1.0;
+    let mut previous = 1.0;
+
+    for k in 0..n.saturating_sub(1) {
+        // Pick the largest entry of the column as the pivot, as dividing by a tiny one amplifies rounding.
+        let pivot = (k..n)
+            .max_by(|&x, &y| a[x * n + k].abs().total_cmp(&a[y * n + k].abs()))
+            .unwrap();
+        if a[pivot * n + k] == 0.0 {
+            return 0.0;
+        }
+
+        if pivot != k {
+            for j in 0..n {
+                a.swap(k * n + j, pivot * n + j);
+            }
+            sign = -sign;
+        }
+
+        for i in k + 1..n {
+            for j in k + 1..n {
+                a[i * n + j] =
+                    (a[i * n + j] * a[k * n + k] - a[i * n + k] * a[k * n + j]) / previous;
+            }
+        }
+
+        previous = a[k * n + k];
+    }
+
+    match n {
+        0 => 1.0,
+        _ => sign * a[n * n - 1],
+    }
+}
+
+#[cfg(test)]
+mod cofactor_tests {
+    use super::*;
+
+    fn example() -> Matrix<3, 3> {
+        Matrix::new(vec![2.0, -1.0, 0.0, 1.0, 3.0, 4.0, 0.0, 5.0, -2.0]).unwrap()
+    }
+
+    #[test]
+    fn test_trace_and_determinant() {
+        let matrix = example();
+
+        assert_eq!(matrix.trace(), 3.0);
+        assert_eq!(matrix.determinant(), -54.0);
+
+        let swapped: Matrix<3, 3> =
+            Matrix::new(vec![0.0, 1.0, 2.0, 1.0, 0.0, 3.0, 4.0, -3.0, 8.0]).unwrap();
+        assert_eq!(swapped.determinant(), -2.0);
+
+        let singular: Matrix<3, 3> =
+            Matrix::new(vec![1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 0.0, 1.0, 1.0]).unwrap();
+        assert_eq!(singular.determinant(), 0.0);
+
+        let two: Matrix<2, 2> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert_eq!(two.determinant(), two.det());
+    }
+
+    #[test]
+    fn test_minors_and_cofactors() {
+        let matrix = example();
+
+        let submatrix: Matrix<2, 2> = matrix.submatrix(0, 1).unwrap();
+        assert_eq!(submatrix.data(), &[1.0, 4.0, 0.0, -2.0]);
+        assert!(matrix.submatrix::<3>(0, 1).is_err());
+
+        assert_eq!(matrix.minor(0, 1).unwrap(), -2.0);
+        assert_eq!(matrix.cofactor(0, 1).unwrap(), 2.0);
+        assert!(matches!(
+            matrix.minor(3, 0),
+            Err(MatrixError::UndefinedIndex)
+        ));
+
+        assert_eq!(
+            matrix.cofactor_matrix().data(),
+            &[-26.0, 2.0, 5.0, -2.0, -4.0, -10.0, -4.0, -8.0, 7.0]
+        );
+    }
+
+    #[test]
+    fn test_adjugate() {
+        let matrix = example();
+        let adjugate = matrix.adjugate();
+
+        assert_eq!(
+            adjugate.data(),
+            &[-26.0, -2.0, -4.0, 2.0, -4.0, -8.0, 5.0, -10.0, 7.0]
+        );
+
+        // A adj(A) = det(A) I, exactly for integer matrices.
+        let product = matrix.multiply(&adjugate);
+        assert_eq!(
+            product.data(),
+            &[-54.0, 0.0, 0.0, 0.0, -54.0, 0.0, 0.0, 0.0, -54.0]
+        );
+
+        let larger: Matrix<3, 3> =
+            Matrix::new(vec![2.0, -3.0, 7.0, 5.0, 11.0, -13.0, 17.0, 19.0, 23.0]).unwrap();
+        let adjugate = larger.adjugate();
+        assert_eq!(
+            adjugate.data(),
+            &[500.0, 202.0, -38.0, -336.0, -73.0, 61.0, -92.0, -89.0, 37.0]
+        );
+        assert_eq!(
+            larger.multiply(&adjugate).data(),
+            &[1364.0, 0.0, 0.0, 0.0, 1364.0, 0.0, 0.0, 0.0, 1364.0]
+        );
+
+        // The 2x2 inverse is the adjugate divided by the determinant.
+        let two: Matrix<2, 2> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+        let mut inverse = two.adjugate();
+        inverse.scalar_multiply(1.0 / two.det());
+        assert_eq!(inverse, two.inv());
+
+        let singular: Matrix<3, 3> =
+            Matrix::new(vec![1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 0.0, 1.0, 1.0]).unwrap();
+        assert_eq!(
+            singular.adjugate().data(),
+            &[-2.0, 1.0, 0.0, -2.0, 1.0, 0.0, 2.0, -1.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn test_determinant_with_tiny_leading_pivot() {
+        // Without pivoting, the first step divides through by 1e-20 and loses the answer completely.
+        let matrix: Matrix<3, 3> =
+            Matrix::new(vec![1e-20, 1.0, 1.0, 1.0, 1.0, 0.0, 1.0, 0.0, 1.0]).unwrap();
+
+        assert!((matrix.determinant() - -2.0).abs() < 1e-12);
+    }
+}
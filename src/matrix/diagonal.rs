@@ -0,0 +1,262 @@
+//! Diagonal matrices and symmetric matrices in packed storage.
+
+use crate::{error::MatrixError, matrix::Matrix, vector::VectorN};
+
+#[derive(Debug, Clone, PartialEq)]
+/// An `N` x `N` matrix that is zero off the main diagonal, stored as the vector of its diagonal.
+pub struct Diagonal<const N: usize> {
+    diagonal: VectorN<N>,
+}
+
+impl<const N: usize> Diagonal<N> {
+    /// Creates a diagonal matrix with `diagonal` on its main diagonal.
+    pub fn new(diagonal: VectorN<N>) -> Self {
+        Self { diagonal }
+    }
+
+    /// Returns the `N` x `N` identity matrix.
+    pub fn identity() -> Self {
+        Self::new(VectorN::new([1.0; N]))
+    }
+
+    /// Takes the main diagonal of `matrix` and drops the rest.
+    pub fn from_dense(matrix: &Matrix<N, N>) -> Self {
+        Self::new(VectorN::new(std::array::from_fn(|i| {
+            matrix.matrix[i * N + i]
+        })))
+    }
+
+    /// Returns the main diagonal.
+    pub fn diagonal(&self) -> &VectorN<N> {
+        &self.diagonal
+    }
+
+    /// Returns the matrix as a plain dense matrix.
+    pub fn to_dense(&self) -> Matrix<N, N> {
+        let mut matrix = vec![0.0; N * N];
+
+        for (i, &entry) in self.diagonal.data().iter().enumerate() {
+            matrix[i * N + i] = entry;
+        }
+
+        Matrix { matrix }
+    }
+
+    /// Returns the sum of the diagonal.
+    pub fn trace(&self) -> f64 {
+        self.diagonal.data().iter().sum()
+    }
+
+    /// Returns the product of the diagonal.
+    pub fn determinant(&self) -> f64 {
+        self.diagonal.data().iter().product()
+    }
+
+    /// Returns the inverse, or a `Singular` error if an entry of the diagonal is zero.
+    pub fn inverse(&self) -> Result<Diagonal<N>, MatrixError> {
+        if self.diagonal.data().contains(&0.0) {
+            return Err(MatrixError::Singular);
+        }
+
+        Ok(Self::new(self.diagonal.map(|entry| 1.0 / entry)))
+    }
+
+    /// Returns the product of the matrix and `x`.
+    pub fn mul_vector(&self, x: &VectorN<N>) -> VectorN<N> {
+        self.diagonal.zip_map(x, |d, x| d * x)
+    }
+
+    /// Returns the product of two diagonal matrices, which is diagonal again.
+    pub fn multiply(&self, other: &Diagonal<N>) -> Diagonal<N> {
+        Self::new(self.mul_vector(&other.diagonal))
+    }
+
+    /// Returns `DA`, which scales row `i` of `matrix` by entry `i` of the diagonal.
+    pub fn multiply_matrix<const C: usize>(&self, matrix: &Matrix<N, C>) -> Matrix<N, C> {
+        let d = self.diagonal.data();
+        let matrix = (0..N * C)
+            .map(|index| d[index / C] * matrix.matrix[index])
+            .collect();
+
+        Matrix { matrix }
+    }
+
+    /// Solves `Dx = b`, returning a `Singular` error if an entry of the diagonal is zero.
+    pub fn solve(&self, b: &VectorN<N>) -> Result<VectorN<N>, MatrixError> {
+        Ok(self.inverse()?.mul_vector(b))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A symmetric `N` x `N` matrix that only stores its upper triangle, row by row.
+pub struct SymmetricPacked<const N: usize> {
+    data: Vec<f64>,
+}
+
+impl<const N: usize> SymmetricPacked<N> {
+    /// Creates a symmetric matrix from its upper triangle in row-major order, which has to have
+    /// `N(N + 1)/2` entries.
+    pub fn new(data: Vec<f64>) -> Result<Self, MatrixError> {
+        if data.len() != N * (N + 1) / 2 {
+            return Err(MatrixError::InvalidDimensions);
+        }
+
+        Ok(Self { data })
+    }
+
+    /// Takes the upper triangle of `matrix`, ignoring what is below the diagonal.
+    pub fn from_upper(matrix: &Matrix<N, N>) -> Self {
+        let data = (0..N)
+            .flat_map(|i| &matrix.matrix[i * N + i..(i + 1) * N])
+            .copied()
+            .collect();
+
+        Self { data }
+    }
+
+    /// Packs a symmetric matrix, returning an `InvalidOperation` error if it is not exactly symmetric.
+    pub fn from_dense(matrix: &Matrix<N, N>) -> Result<Self, MatrixError> {
+        for i in 0..N {
+            for j in i + 1..N {
+                if matrix.matrix[i * N + j] != matrix.matrix[j * N + i] {
+                    return Err(MatrixError::InvalidOperation(format!(
+                        "the matrix is not symmetric at row {i} and column {j}"
+                    )));
+                }
+            }
+        }
+
+        Ok(Self::from_upper(matrix))
+    }
+
+    /// Returns the packed upper triangle.
+    pub fn data(&self) -> &[f64] {
+        &self.data
+    }
+
+    /// Returns the entry at row `i` and column `j`.
+    pub fn get(&self, i: usize, j: usize) -> Option<f64> {
+        if i >= N || j >= N {
+            return None;
+        }
+
+        Some(self.data[Self::index(i, j)])
+    }
+
+    /// Sets the entries at `(i, j)` and `(j, i)`, returning an `UndefinedIndex` error if they are outside
+    /// the matrix.
+    pub fn set(&mut self, entry: f64, i: usize, j: usize) -> Result<(), MatrixError> {
+        if i >= N || j >= N {
+            return Err(MatrixError::UndefinedIndex);
+        }
+
+        self.data[Self::index(i, j)] = entry;
+
+        Ok(())
+    }
+
+    /// Returns the matrix as a plain dense matrix.
+    pub fn to_dense(&self) -> Matrix<N, N> {
+        let mut matrix = vec![0.0; N * N];
+
+        for i in 0..N {
+            for j in i..N {
+                let entry = self.data[Self::index(i, j)];
+                matrix[i * N + j] = entry;
+                matrix[j * N + i] = entry;
+            }
+        }
+
+        Matrix { matrix }
+    }
+
+    /// Returns the product of the matrix and `x`, reading each stored entry once.
+    pub fn mul_vector(&self, x: &VectorN<N>) -> VectorN<N> {
+        let x = x.data();
+        let mut y = [0.0; N];
+        let mut entries = self.data.iter();
+
+        for i in 0..N {
+            for j in i..N {
+                let entry = *entries.next().unwrap();
+                y[i] += entry * x[j];
+                if i != j {
+                    y[j] += entry * x[i];
+                }
+            }
+        }
+
+        VectorN::new(y)
+    }
+
+    /// The position of entry `(i, j)` in the packed storage.
+    fn index(i: usize, j: usize) -> usize {
+        let (i, j) = if i <= j { (i, j) } else { (j, i) };
+
+        // Row `i` starts after the `N - k` entries of every row `k` above it.
+        i * N - i * i.saturating_sub(1) / 2 + j - i
+    }
+}
+
+#[cfg(test)]
+mod diagonal_tests {
+    use super::*;
+
+    #[test]
+    fn test_diagonal() {
+        let diagonal = Diagonal::new(VectorN::new([2.0, -1.0, 4.0]));
+        let dense = diagonal.to_dense();
+
+        assert_eq!(Diagonal::from_dense(&dense), diagonal);
+        assert_eq!(diagonal.determinant(), dense.determinant());
+        assert_eq!(diagonal.trace(), 5.0);
+        assert_eq!(
+            diagonal.inverse().unwrap().to_dense().multiply(&dense),
+            Diagonal::<3>::identity().to_dense()
+        );
+        assert_eq!(
+            diagonal.multiply(&diagonal).to_dense(),
+            dense.multiply(&dense)
+        );
+
+        let matrix: Matrix<3, 2> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+        assert_eq!(diagonal.multiply_matrix(&matrix), dense.multiply(&matrix));
+
+        let b = VectorN::new([2.0, 3.0, 1.0]);
+        assert_eq!(diagonal.solve(&b).unwrap().data(), &[1.0, -3.0, 0.25]);
+        assert_eq!(diagonal.mul_vector(&b).data(), &[4.0, -3.0, 4.0]);
+
+        let singular = Diagonal::new(VectorN::new([1.0, 0.0]));
+        assert!(matches!(singular.inverse(), Err(MatrixError::Singular)));
+    }
+
+    #[test]
+    fn test_symmetric_packed() {
+        let dense: Matrix<3, 3> =
+            Matrix::new(vec![4.0, 1.0, 2.0, 1.0, 5.0, 3.0, 2.0, 3.0, 6.0]).unwrap();
+        let mut packed = SymmetricPacked::from_dense(&dense).unwrap();
+
+        assert_eq!(packed.data(), &[4.0, 1.0, 2.0, 5.0, 3.0, 6.0]);
+        assert_eq!(packed.to_dense(), dense);
+        assert_eq!(packed.get(2, 1), Some(3.0));
+        assert_eq!(packed.get(3, 1), None);
+
+        let x = VectorN::new([1.0, -2.0, 0.5]);
+        assert_eq!(packed.mul_vector(&x).data(), &[3.0, -7.5, -1.0]);
+
+        packed.set(-1.0, 2, 0).unwrap();
+        assert_eq!(packed.get(0, 2), Some(-1.0));
+        assert!(packed.set(1.0, 0, 3).is_err());
+
+        let asymmetric: Matrix<2, 2> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert!(matches!(
+            SymmetricPacked::from_dense(&asymmetric),
+            Err(MatrixError::InvalidOperation(_))
+        ));
+        assert_eq!(
+            SymmetricPacked::from_upper(&asymmetric).data(),
+            &[1.0, 2.0, 4.0]
+        );
+        assert!(SymmetricPacked::<3>::new(vec![1.0; 5]).is_err());
+    }
+}
@@ -0,0 +1,226 @@
+//! Reduced row echelon form and the fundamental subspaces of a matrix.
+
+use crate::{matrix::Matrix, vector::VectorN};
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    /// Returns the reduced row echelon form of the matrix along with its pivot columns.
+    ///
+    /// Entries no larger than `max(R, C) * ε * ||A||max` are treated as zero.
+    pub fn rref(&self) -> (Matrix<R, C>, Vec<usize>) {
+        self.rref_with_tolerance(self.default_tolerance())
+    }
+
+    /// Returns the reduced row echelon form of the matrix along with its pivot columns, treating entries
+    /// no larger than `tolerance` as zero.
+    pub fn rref_with_tolerance(&self, tolerance: f64) -> (Matrix<R, C>, Vec<usize>) {
+        let (matrix, pivots) = rref(R, C, &self.matrix, tolerance);
+
+        (Matrix { matrix }, pivots)
+    }
+
+    /// Returns the rank of the matrix, the number of pivots left after treating entries no larger than
+    /// `tolerance` as zero.
+    pub fn rank(&self, tolerance: f64) -> usize {
+        rref(R, C, &self.matrix, tolerance).1.len()
+    }
+
+    /// Returns an orthonormal basis of the null space, the vectors `x` with `Ax = 0`.
+    pub fn null_space(&self, tolerance: f64) -> Vec<VectorN<C>> {
+        let (reduced, pivots) = rref(R, C, &self.matrix, tolerance);
+
+        // Every free column gives one basis vector, found by setting that variable to one.
+        let basis = (0..C)
+            .filter(|j| !pivots.contains(j))
+            .map(|free| {
+                let mut x = vec![0.0; C];
+                x[free] = 1.0;
+
+                for (row, &pivot) in pivots.iter().enumerate() {
+                    x[pivot] = -reduced[row * C + free];
+                }
+
+                x
+            })
+            .collect();
+
+        VectorN::orthonormal_span(&to_vectors(basis), 0.0)
+    }
+
+    /// Returns an orthonormal basis of the column space, spanned by the pivot columns.
+    pub fn column_space(&self, tolerance: f64) -> Vec<VectorN<R>> {
+        let (_, pivots) = rref(R, C, &self.matrix, tolerance);
+
+        let basis = pivots
+            .iter()
+            .map(|&j| (0..R).map(|i| self.matrix[i * C + j]).collect())
+            .collect();
+
+        VectorN::orthonormal_span(&to_vectors(basis), 0.0)
+    }
+
+    /// Returns an orthonormal basis of the row space, spanned by the nonzero rows of the reduced form.
+    pub fn row_space(&self, tolerance: f64) -> Vec<VectorN<C>> {
+        let (reduced, pivots) = rref(R, C, &self.matrix, tolerance);
+
+        let basis = reduced
+            .chunks(C.max(1))
+            .take(pivots.len())
+            .map(|row| row.to_vec())
+            .collect();
+
+        VectorN::orthonormal_span(&to_vectors(basis), 0.0)
+    }
+
+    fn default_tolerance(&self) -> f64 {
+        let largest = self.matrix.iter().map(|f| f.abs()).fold(0.0, f64::max);
+
+        R.max(C) as f64 * f64::EPSILON * largest
+    }
+}
+
+/// Reduces a `rows` x `columns` row-major matrix with partial pivoting, returning it and its pivot
+/// columns.
+fn rref(rows: usize, columns: usize, data: &[f64], tolerance: f64) -> (Vec<f64>, Vec<usize>) {
+    let mut a = data.to_vec();
+    let mut pivots = Vec::new();
+
+    for j in 0..columns {
+        let r = pivots.len();
+        if r == rows {
+            break;
+        }
+
+        let pivot = (r..rows)
+            .max_by(|&x, &y| {
+                a[x * columns + j]
+                    .abs()
+                    .total_cmp(&a[y * columns + j].abs())
+            })
+            .unwrap();
+
+        if a[pivot * columns + j].abs() <= tolerance {
+            // The column is negligible below the current row, so clear it.
+            (r..rows).for_each(|i| a[i * columns + j] = 0.0);
+            continue;
+        }
+
+        for k in 0..columns {
+            a.swap(r * columns + k, pivot * columns + k);
+        }
+
+        let scale = a[r * columns + j];
+        a[r * columns..(r + 1) * columns]
+            .iter_mut()
+            .for_each(|f| *f /= scale);
+        a[r * columns + j] = 1.0;
+
+        for i in (0..rows).filter(|&i| i != r) {
+            let factor = a[i * columns + j];
+            if factor == 0.0 {
+                continue;
+            }
+
+            for k in 0..columns {
+                a[i * columns + k] -= factor * a[r * columns + k];
+            }
+            a[i * columns + j] = 0.0;
+        }
+
+        pivots.push(j);
+    }
+
+    (a, pivots)
+}
+
+fn to_vectors<const N: usize>(vectors: Vec<Vec<f64>>) -> Vec<VectorN<N>> {
+    vectors
+        .into_iter()
+        .map(|v| VectorN::new(v.try_into().unwrap()))
+        .collect()
+}
+
+#[cfg(test)]
+mod echelon_tests {
+    use super::*;
+
+    fn example() -> Matrix<3, 4> {
+        Matrix::new(vec![
+            1.0, 2.0, 0.0, 3.0, //
+            2.0, 4.0, 1.0, 7.0, //
+            3.0, 6.0, 1.0, 10.0,
+        ])
+        .unwrap()
+    }
+
+    fn assert_orthonormal<const N: usize>(basis: &[VectorN<N>]) {
+        for (i, a) in basis.iter().enumerate() {
+            for (j, b) in basis.iter().enumerate() {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((a.dotproduct(b).unwrap() - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rref() {
+        let (reduced, pivots) = example().rref();
+
+        let expected = [
+            1.0, 2.0, 0.0, 3.0, //
+            0.0, 0.0, 1.0, 1.0, //
+            0.0, 0.0, 0.0, 0.0,
+        ];
+
+        assert_eq!(pivots, [0, 2]);
+        for (f, expected) in reduced.data().iter().zip(expected) {
+            assert!((f - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_rank() {
+        assert_eq!(example().rank(1e-12), 2);
+
+        let identity: Matrix<3, 3> = Matrix::new_identity_matrix(3);
+        assert_eq!(identity.rank(1e-12), 3);
+
+        // A tiny perturbation only counts above the tolerance.
+        let nearly_singular: Matrix<2, 2> = Matrix::new(vec![1.0, 1.0, 1.0, 1.0 + 1e-9]).unwrap();
+        assert_eq!(nearly_singular.rank(1e-6), 1);
+        assert_eq!(nearly_singular.rank(1e-12), 2);
+    }
+
+    #[test]
+    fn test_null_space() {
+        let matrix = example();
+        let null_space = matrix.null_space(1e-12);
+
+        assert_eq!(null_space.len(), 2);
+        assert_orthonormal(&null_space);
+
+        for x in &null_space {
+            let product: Matrix<3, 1> = matrix.multiply(&Matrix::new(x.data().to_vec()).unwrap());
+            assert!(product.data().iter().all(|f| f.abs() < 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_column_and_row_spaces() {
+        let matrix = example();
+
+        let columns = matrix.column_space(1e-12);
+        assert_eq!(columns.len(), 2);
+        assert_orthonormal(&columns);
+
+        let rows = matrix.row_space(1e-12);
+        assert_eq!(rows.len(), 2);
+        assert_orthonormal(&rows);
+
+        // The row space is orthogonal to the null space.
+        for x in matrix.null_space(1e-12) {
+            for row in &rows {
+                assert!(row.dotproduct(&x).unwrap().abs() < 1e-12);
+            }
+        }
+    }
+}
@@ -0,0 +1,442 @@
+//! Elementary row and column operations, with an optional log of every operation applied.
+//!
+//! Every row operation is the same as multiplying by an elementary matrix from the left, and every
+//! column operation the same as multiplying by one from the right. Rows and columns are numbered from
+//! zero in the API but from one in the human-readable descriptions.
+
+use std::fmt;
+
+use crate::{error::MatrixError, matrix::Matrix};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A single elementary row or column operation.
+pub enum ElementaryOp {
+    /// Swaps two rows.
+    SwapRows(usize, usize),
+    /// Multiplies a row by a nonzero factor.
+    ScaleRow { row: usize, factor: f64 },
+    /// Adds `factor` times the `source` row to the `target` row.
+    AddRowMultiple {
+        target: usize,
+        source: usize,
+        factor: f64,
+    },
+    /// Swaps two columns.
+    SwapColumns(usize, usize),
+    /// Multiplies a column by a nonzero factor.
+    ScaleColumn { column: usize, factor: f64 },
+    /// Adds `factor` times the `source` column to the `target` column.
+    AddColumnMultiple {
+        target: usize,
+        source: usize,
+        factor: f64,
+    },
+}
+
+impl ElementaryOp {
+    /// Checks whether the operation acts on rows rather than columns.
+    pub fn is_row_operation(&self) -> bool {
+        matches!(
+            self,
+            Self::SwapRows(..) | Self::ScaleRow { .. } | Self::AddRowMultiple { .. }
+        )
+    }
+
+    /// Returns the `N` x `N` elementary matrix of the operation, found by applying it to the identity.
+    ///
+    /// Row operations are performed by multiplying with this matrix from the left, column operations by
+    /// multiplying from the right.
+    pub fn to_elementary_matrix<const N: usize>(&self) -> Result<Matrix<N, N>, MatrixError> {
+        let mut identity: Matrix<N, N> = Matrix::new_identity_matrix(N);
+        identity.apply_elementary(*self, None)?;
+
+        Ok(identity)
+    }
+
+    /// Checks that the operation is invertible and fits a matrix with `lines` rows or columns.
+    fn validate(&self, lines: usize) -> Result<(), MatrixError> {
+        let (indices, factor, distinct) = match *self {
+            Self::SwapRows(a, b) | Self::SwapColumns(a, b) => ([a, b], 1.0, true),
+            Self::ScaleRow { row: a, factor } | Self::ScaleColumn { column: a, factor } => {
+                ([a, a], factor, true)
+            }
+            Self::AddRowMultiple {
+                target,
+                source,
+                factor,
+            }
+            | Self::AddColumnMultiple {
+                target,
+                source,
+                factor,
+            } => ([target, source], factor, target != source),
+        };
+
+        if indices.iter().any(|&i| i >= lines) {
+            return Err(MatrixError::UndefinedIndex);
+        }
+
+        if !distinct {
+            return Err(MatrixError::InvalidOperation(format!(
+                "{} adds a multiple of a line to itself",
+                self
+            )));
+        }
+
+        let scales = matches!(self, Self::ScaleRow { .. } | Self::ScaleColumn { .. });
+        if (scales && factor == 0.0) || !factor.is_finite() {
+            return Err(MatrixError::InvalidOperation(format!(
+                "{} has a zero or non-finite factor",
+                self
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for ElementaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = if self.is_row_operation() { "R" } else { "C" };
+
+        match *self {
+            Self::SwapRows(a, b) | Self::SwapColumns(a, b) => {
+                write!(f, "{name}{} <-> {name}{}", a + 1, b + 1)
+            }
+            Self::ScaleRow { row: a, factor } | Self::ScaleColumn { column: a, factor } => {
+                write!(f, "{name}{} -> {} * {name}{}", a + 1, factor, a + 1)
+            }
+            Self::AddRowMultiple {
+                target,
+                source,
+                factor,
+            }
+            | Self::AddColumnMultiple {
+                target,
+                source,
+                factor,
+            } => {
+                let sign = if factor.is_sign_negative() { '-' } else { '+' };
+
+                write!(
+                    f,
+                    "{name}{} -> {name}{} {} {} * {name}{}",
+                    target + 1,
+                    target + 1,
+                    sign,
+                    factor.abs(),
+                    source + 1
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// A record of the elementary operations applied to a matrix, in order.
+pub struct OperationLog {
+    operations: Vec<ElementaryOp>,
+}
+
+impl OperationLog {
+    /// Creates an empty log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the logged operations, oldest first.
+    pub fn operations(&self) -> &[ElementaryOp] {
+        &self.operations
+    }
+
+    /// Returns the number of logged operations.
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// Checks whether nothing has been logged.
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Returns a human-readable description of every step.
+    pub fn steps(&self) -> Vec<String> {
+        self.operations.iter().map(|op| op.to_string()).collect()
+    }
+
+    /// Returns the elementary matrix of every logged row operation, oldest first, skipping column
+    /// operations.
+    ///
+    /// Multiplying them together, newest on the left, gives the matrix that performs all of the row
+    /// operations at once.
+    pub fn row_matrices<const R: usize>(&self) -> Result<Vec<Matrix<R, R>>, MatrixError> {
+        self.operations
+            .iter()
+            .filter(|op| op.is_row_operation())
+            .map(|op| op.to_elementary_matrix())
+            .collect()
+    }
+}
+
+impl fmt::Display for OperationLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, op) in self.operations.iter().enumerate() {
+            writeln!(f, "{}. {}", i + 1, op)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    /// Swaps rows `a` and `b`.
+    pub fn swap_rows(&mut self, a: usize, b: usize) -> Result<(), MatrixError> {
+        self.apply_elementary(ElementaryOp::SwapRows(a, b), None)
+    }
+
+    /// Multiplies row `row` by the nonzero `factor`.
+    pub fn scale_row(&mut self, row: usize, factor: f64) -> Result<(), MatrixError> {
+        self.apply_elementary(ElementaryOp::ScaleRow { row, factor }, None)
+    }
+
+    /// Adds `factor` times row `source` to row `target`.
+    pub fn add_row_multiple(
+        &mut self,
+        target: usize,
+        source: usize,
+        factor: f64,
+    ) -> Result<(), MatrixError> {
+        self.apply_elementary(
+            ElementaryOp::AddRowMultiple {
+                target,
+                source,
+                factor,
+            },
+            None,
+        )
+    }
+
+    /// Swaps columns `a` and `b`.
+    pub fn swap_columns(&mut self, a: usize, b: usize) -> Result<(), MatrixError> {
+        self.apply_elementary(ElementaryOp::SwapColumns(a, b), None)
+    }
+
+    /// Multiplies column `column` by the nonzero `factor`.
+    pub fn scale_column(&mut self, column: usize, factor: f64) -> Result<(), MatrixError> {
+        self.apply_elementary(ElementaryOp::ScaleColumn { column, factor }, None)
+    }
+
+    /// Adds `factor` times column `source` to column `target`.
+    pub fn add_column_multiple(
+        &mut self,
+        target: usize,
+        source: usize,
+        factor: f64,
+    ) -> Result<(), MatrixError> {
+        self.apply_elementary(
+            ElementaryOp::AddColumnMultiple {
+                target,
+                source,
+                factor,
+            },
+            None,
+        )
+    }
+
+    /// Applies an elementary operation, recording it in `log` if one is given.
+    ///
+    /// Returns an `UndefinedIndex` error if a row or column is out of range, and an `InvalidOperation`
+    /// error if the operation is not invertible. Nothing is changed or logged in either case.
+    pub fn apply_elementary(
+        &mut self,
+        op: ElementaryOp,
+        log: Option<&mut OperationLog>,
+    ) -> Result<(), MatrixError> {
+        op.validate(if op.is_row_operation() { R } else { C })?;
+
+        let (step, stride, length) = if op.is_row_operation() {
+            (C, 1, C)
+        } else {
+            (1, C, R)
+        };
+        // The index of the `k`th entry of row or column `line`.
+        let at = |line: usize, k: usize| line * step + k * stride;
+
+        match op {
+            ElementaryOp::SwapRows(a, b) | ElementaryOp::SwapColumns(a, b) => {
+                for k in 0..length {
+                    self.matrix.swap(at(a, k), at(b, k));
+                }
+            }
+            ElementaryOp::ScaleRow { row: a, factor }
+            | ElementaryOp::ScaleColumn { column: a, factor } => {
+                for k in 0..length {
+                    self.matrix[at(a, k)] *= factor;
+                }
+            }
+            ElementaryOp::AddRowMultiple {
+                target,
+                source,
+                factor,
+            }
+            | ElementaryOp::AddColumnMultiple {
+                target,
+                source,
+                factor,
+            } => {
+                for k in 0..length {
+                    self.matrix[at(target, k)] += factor * self.matrix[at(source, k)];
+                }
+            }
+        }
+
+        if let Some(log) = log {
+            log.operations.push(op);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod elementary_tests {
+    use super::*;
+
+    fn example() -> Matrix<2, 3> {
+        Matrix::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap()
+    }
+
+    #[test]
+    fn test_row_operations() {
+        let mut matrix = example();
+
+        matrix.swap_rows(0, 1).unwrap();
+        assert_eq!(matrix.data(), &[4.0, 5.0, 6.0, 1.0, 2.0, 3.0]);
+
+        matrix.scale_row(1, 2.0).unwrap();
+        assert_eq!(matrix.data(), &[4.0, 5.0, 6.0, 2.0, 4.0, 6.0]);
+
+        matrix.add_row_multiple(0, 1, -2.0).unwrap();
+        assert_eq!(matrix.data(), &[0.0, -3.0, -6.0, 2.0, 4.0, 6.0]);
+    }
+
+    #[test]
+    fn test_column_operations() {
+        let mut matrix = example();
+
+        matrix.swap_columns(0, 2).unwrap();
+        assert_eq!(matrix.data(), &[3.0, 2.0, 1.0, 6.0, 5.0, 4.0]);
+
+        matrix.scale_column(1, 0.5).unwrap();
+        assert_eq!(matrix.data(), &[3.0, 1.0, 1.0, 6.0, 2.5, 4.0]);
+
+        matrix.add_column_multiple(2, 0, 1.0).unwrap();
+        assert_eq!(matrix.data(), &[3.0, 1.0, 4.0, 6.0, 2.5, 10.0]);
+    }
+
+    #[test]
+    fn test_invalid_operations() {
+        let mut matrix = example();
+
+        assert!(matches!(
+            matrix.swap_rows(0, 2),
+            Err(MatrixError::UndefinedIndex)
+        ));
+        assert!(matches!(
+            matrix.scale_row(0, 0.0),
+            Err(MatrixError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            matrix.add_column_multiple(1, 1, 2.0),
+            Err(MatrixError::InvalidOperation(_))
+        ));
+
+        assert_eq!(matrix, example());
+    }
+
+    #[test]
+    fn test_elementary_matrices() {
+        let ops = [
+            ElementaryOp::SwapRows(0, 1),
+            ElementaryOp::ScaleRow {
+                row: 0,
+                factor: 3.0,
+            },
+            ElementaryOp::AddRowMultiple {
+                target: 1,
+                source: 0,
+                factor: -1.5,
+            },
+        ];
+
+        for op in ops {
+            let mut matrix = example();
+            matrix.apply_elementary(op, None).unwrap();
+
+            let elementary: Matrix<2, 2> = op.to_elementary_matrix().unwrap();
+            assert_eq!(elementary.multiply(&example()), matrix);
+        }
+
+        let op = ElementaryOp::AddColumnMultiple {
+            target: 0,
+            source: 2,
+            factor: 2.0,
+        };
+        let mut matrix = example();
+        matrix.apply_elementary(op, None).unwrap();
+
+        let elementary: Matrix<3, 3> = op.to_elementary_matrix().unwrap();
+        assert_eq!(example().multiply(&elementary), matrix);
+    }
+
+    #[test]
+    fn test_operation_log() {
+        let mut matrix = example();
+        let mut log = OperationLog::new();
+
+        matrix
+            .apply_elementary(
+                ElementaryOp::AddRowMultiple {
+                    target: 1,
+                    source: 0,
+                    factor: -4.0,
+                },
+                Some(&mut log),
+            )
+            .unwrap();
+        matrix
+            .apply_elementary(
+                ElementaryOp::ScaleRow {
+                    row: 1,
+                    factor: -1.0 / 3.0,
+                },
+                Some(&mut log),
+            )
+            .unwrap();
+        matrix
+            .apply_elementary(ElementaryOp::SwapColumns(0, 1), Some(&mut log))
+            .unwrap();
+
+        assert_eq!(log.len(), 3);
+        assert_eq!(
+            log.steps(),
+            [
+                "R2 -> R2 - 4 * R1",
+                "R2 -> -0.3333333333333333 * R2",
+                "C1 <-> C2",
+            ]
+        );
+        assert_eq!(
+            log.to_string(),
+            "1. R2 -> R2 - 4 * R1\n2. R2 -> -0.3333333333333333 * R2\n3. C1 <-> C2\n"
+        );
+
+        // The row operations alone take the matrix to echelon form.
+        let mut rows_only = example();
+        for elementary in log.row_matrices::<2>().unwrap() {
+            rows_only = elementary.multiply(&rows_only);
+        }
+        rows_only.swap_columns(0, 1).unwrap();
+        assert_eq!(rows_only, matrix);
+    }
+}
@@ -0,0 +1,460 @@
+//! Step-by-step Gauss–Jordan elimination of augmented systems, for showing the working.
+//!
+//! The last column of the matrix is treated as the right-hand side `b` of the system `Ax = b`. The
+//! pivot in each column is the first nonzero entry at or below the current row, as it would be chosen
+//! by hand, and the system is reduced all the way to reduced row echelon form.
+
+use std::fmt::Write;
+
+use crate::{
+    error::MatrixError,
+    matrix::{
+        elementary::{ElementaryOp, OperationLog},
+        Matrix,
+    },
+};
+
+/// Entries no larger than this, relative to the largest entry, are treated as zero.
+const RELATIVE_TOLERANCE: f64 = 1e-12;
+
+#[derive(Debug, Clone, PartialEq)]
+/// One elementary row operation of an elimination, along with the matrix it produced.
+pub struct EliminationStep<const R: usize, const C: usize> {
+    /// The row and column of the pivot being worked on.
+    pub pivot: (usize, usize),
+    /// The row operation performed.
+    pub operation: ElementaryOp,
+    /// The matrix after the operation.
+    pub matrix: Matrix<R, C>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// What the reduced system says about its solutions.
+pub enum SystemOutcome {
+    /// There is exactly one solution.
+    Unique(Vec<f64>),
+    /// There are infinitely many solutions, parametrized by the given free variables.
+    Infinite { free_variables: Vec<usize> },
+    /// There are no solutions.
+    Inconsistent,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The full working of a Gauss–Jordan elimination.
+pub struct EliminationTrace<const R: usize, const C: usize> {
+    initial: Matrix<R, C>,
+    steps: Vec<EliminationStep<R, C>>,
+    pivots: Vec<(usize, usize)>,
+    tolerance: f64,
+}
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    /// Reduces the augmented matrix `[A | b]` to reduced row echelon form, recording every step.
+    ///
+    /// Subnormal entries are treated as zero, as their reciprocals overflow. Returns an `InvalidOperation`
+    /// error if a NaN entry makes one of the row operations invalid.
+    pub fn elimination_trace(&self) -> Result<EliminationTrace<R, C>, MatrixError> {
+        let largest = self.matrix.iter().map(|f| f.abs()).fold(0.0, f64::max);
+        // Subnormal entries always count as zero, as dividing by them overflows.
+        let tolerance = (RELATIVE_TOLERANCE * largest).max(f64::MIN_POSITIVE);
+
+        let mut matrix = self.clone();
+        let mut steps = Vec::new();
+        let mut pivots = Vec::new();
+
+        for column in 0..C.saturating_sub(1) {
+            let row = pivots.len();
+            if row == R {
+                break;
+            }
+
+            let Some(pivot_row) =
+                (row..R).find(|&i| matrix.matrix[i * C + column].abs() > tolerance)
+            else {
+                continue;
+            };
+            let pivot = (row, column);
+
+            if pivot_row != row {
+                let operation = ElementaryOp::SwapRows(row, pivot_row);
+                apply_step(&mut matrix, &mut steps, pivot, operation, None)?;
+            }
+
+            let entry = matrix.matrix[row * C + column];
+            if entry != 1.0 {
+                let operation = ElementaryOp::ScaleRow {
+                    row,
+                    factor: 1.0 / entry,
+                };
+                apply_step(&mut matrix, &mut steps, pivot, operation, Some(1.0))?;
+            }
+
+            for target in (0..R).filter(|&i| i != row) {
+                let factor = matrix.matrix[target * C + column];
+                if factor.abs() <= tolerance {
+                    continue;
+                }
+
+                let operation = ElementaryOp::AddRowMultiple {
+                    target,
+                    source: row,
+                    factor: -factor,
+                };
+                apply_step(&mut matrix, &mut steps, pivot, operation, Some(0.0))?;
+            }
+
+            pivots.push(pivot);
+        }
+
+        Ok(EliminationTrace {
+            initial: self.clone(),
+            steps,
+            pivots,
+            tolerance,
+        })
+    }
+}
+
+impl<const R: usize, const C: usize> EliminationTrace<R, C> {
+    /// Returns the matrix the elimination started from.
+    pub fn initial(&self) -> &Matrix<R, C> {
+        &self.initial
+    }
+
+    /// Returns every step of the elimination in order.
+    pub fn steps(&self) -> &[EliminationStep<R, C>] {
+        &self.steps
+    }
+
+    /// Returns the positions of the pivots as `(row, column)` pairs.
+    pub fn pivots(&self) -> &[(usize, usize)] {
+        &self.pivots
+    }
+
+    /// Returns the reduced row echelon form the elimination ended with.
+    pub fn result(&self) -> &Matrix<R, C> {
+        self.steps.last().map_or(&self.initial, |step| &step.matrix)
+    }
+
+    /// Returns the row operations performed, as an [`OperationLog`].
+    pub fn log(&self) -> OperationLog {
+        let mut log = OperationLog::new();
+        let mut matrix = self.initial.clone();
+
+        for step in &self.steps {
+            matrix
+                .apply_elementary(step.operation, Some(&mut log))
+                .unwrap();
+        }
+
+        log
+    }
+
+    /// Reads off whether the system has no, one or infinitely many solutions.
+    pub fn outcome(&self) -> SystemOutcome {
+        let result = &self.result().matrix;
+        let variables = C.saturating_sub(1);
+
+        // A row of zeros equal to something nonzero can't be satisfied.
+        let inconsistent =
+            (self.pivots.len()..R).any(|i| result[i * C + variables].abs() > self.tolerance);
+        if inconsistent {
+            return SystemOutcome::Inconsistent;
+        }
+
+        if self.pivots.len() == variables {
+            let solution = (0..variables).map(|i| result[i * C + variables]).collect();
+
+            return SystemOutcome::Unique(solution);
+        }
+
+        let free_variables = (0..variables)
+            .filter(|&j| !self.pivots.iter().any(|&(_, column)| column == j))
+            .collect();
+
+        SystemOutcome::Infinite { free_variables }
+    }
+
+    /// Renders the elimination as plain text, with `|` separating the augmented column.
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("Start:\n");
+        text.push_str(&text_matrix(&self.initial));
+
+        for (i, step) in self.steps.iter().enumerate() {
+            writeln!(
+                text,
+                "\nStep {}: pivot at row {}, column {}; {}",
+                i + 1,
+                step.pivot.0 + 1,
+                step.pivot.1 + 1,
+                step.operation
+            )
+            .unwrap();
+            text.push_str(&text_matrix(&step.matrix));
+        }
+
+        text
+    }
+
+    /// Renders the elimination as Markdown, with one table per step.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from("**Start**\n\n");
+        markdown.push_str(&markdown_table(&self.initial));
+
+        for (i, step) in self.steps.iter().enumerate() {
+            write!(
+                markdown,
+                "\n**Step {}.** Pivot at row {}, column {}: `{}`\n\n",
+                i + 1,
+                step.pivot.0 + 1,
+                step.pivot.1 + 1,
+                step.operation
+            )
+            .unwrap();
+            markdown.push_str(&markdown_table(&step.matrix));
+        }
+
+        markdown
+    }
+
+    /// Renders the elimination as a LaTeX `align*` environment of `bmatrix` environments joined by
+    /// arrows labelled with the row operations.
+    pub fn to_latex(&self) -> String {
+        let mut latex = String::from("\\begin{align*}\n");
+        writeln!(latex, "&{}", latex_matrix(&self.initial)).unwrap();
+
+        for step in &self.steps {
+            writeln!(
+                latex,
+                "\\\\\n\\xrightarrow{{{}}} &{}",
+                latex_operation(&step.operation),
+                latex_matrix(&step.matrix)
+            )
+            .unwrap();
+        }
+
+        latex.push_str("\\end{align*}\n");
+        latex
+    }
+}
+
+/// Applies a row operation and records it, setting the entry in the pivot column of the changed row to
+/// `exact` if given, as dividing and subtracting can leave it off by a rounding error.
+fn apply_step<const R: usize, const C: usize>(
+    matrix: &mut Matrix<R, C>,
+    steps: &mut Vec<EliminationStep<R, C>>,
+    pivot: (usize, usize),
+    operation: ElementaryOp,
+    exact: Option<f64>,
+) -> Result<(), MatrixError> {
+    matrix.apply_elementary(operation, None)?;
+
+    let changed = match operation {
+        ElementaryOp::ScaleRow { row, .. } => row,
+        ElementaryOp::AddRowMultiple { target, .. } => target,
+        _ => pivot.0,
+    };
+    if let Some(exact) = exact {
+        matrix.matrix[changed * C + pivot.1] = exact;
+    }
+
+    steps.push(EliminationStep {
+        pivot,
+        operation,
+        matrix: matrix.clone(),
+    });
+
+    Ok(())
+}
+
+/// Formats an entry with at most four decimals, without trailing zeros or negative zero.
+fn format_entry(f: f64) -> String {
+    let rounded = format!("{:.4}", f);
+    let trimmed = rounded.trim_end_matches('0').trim_end_matches('.');
+
+    match trimmed {
+        "-0" => "0".to_string(),
+        _ => trimmed.to_string(),
+    }
+}
+
+fn text_matrix<const R: usize, const C: usize>(matrix: &Matrix<R, C>) -> String {
+    let entries: Vec<String> = matrix.matrix.iter().map(|&f| format_entry(f)).collect();
+    let width = entries.iter().map(|e| e.len()).max().unwrap_or(0);
+
+    let mut text = String::new();
+    for row in entries.chunks(C.max(1)).take(R) {
+        let cells: Vec<String> = row.iter().map(|e| format!("{:>width$}", e)).collect();
+        let (coefficients, rhs) = cells.split_at(C.saturating_sub(1));
+
+        writeln!(text, "[ {} | {} ]", coefficients.join(" "), rhs.join(" ")).unwrap();
+    }
+
+    text
+}
+
+fn markdown_table<const R: usize, const C: usize>(matrix: &Matrix<R, C>) -> String {
+    let mut header: Vec<String> = (1..C).map(|j| format!("x{}", j)).collect();
+    header.push("b".to_string());
+
+    let mut table = format!("| {} |\n|{}\n", header.join(" | "), "---|".repeat(C));
+    for row in matrix.matrix.chunks(C.max(1)).take(R) {
+        let cells: Vec<String> = row.iter().map(|&f| format_entry(f)).collect();
+        writeln!(table, "| {} |", cells.join(" | ")).unwrap();
+    }
+
+    table
+}
+
+fn latex_matrix<const R: usize, const C: usize>(matrix: &Matrix<R, C>) -> String {
+    let rows: Vec<String> = matrix
+        .matrix
+        .chunks(C.max(1))
+        .take(R)
+        .map(|row| {
+            row.iter()
+                .map(|&f| format_entry(f))
+                .collect::<Vec<_>>()
+                .join(" & ")
+        })
+        .collect();
+
+    format!(
+        "\\begin{{bmatrix}} {} \\end{{bmatrix}}",
+        rows.join(" \\\\ ")
+    )
+}
+
+fn latex_operation(operation: &ElementaryOp) -> String {
+    match *operation {
+        ElementaryOp::SwapRows(a, b) => format!("R_{{{}}} \\leftrightarrow R_{{{}}}", a + 1, b + 1),
+        ElementaryOp::ScaleRow { row, factor } => {
+            format!(
+                "R_{{{}}} \\to {} R_{{{}}}",
+                row + 1,
+                format_entry(factor),
+                row + 1
+            )
+        }
+        ElementaryOp::AddRowMultiple {
+            target,
+            source,
+            factor,
+        } => {
+            let sign = if factor.is_sign_negative() { '-' } else { '+' };
+
+            format!(
+                "R_{{{}}} \\to R_{{{}}} {} {} R_{{{}}}",
+                target + 1,
+                target + 1,
+                sign,
+                format_entry(factor.abs()),
+                source + 1
+            )
+        }
+        // The elimination only uses row operations, but column operations are shown all the same.
+        _ => operation.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod elimination_tests {
+    use super::*;
+
+    fn example() -> Matrix<2, 3> {
+        // x + 2y = 5, 3x + 4y = 6
+        Matrix::new(vec![1.0, 2.0, 5.0, 3.0, 4.0, 6.0]).unwrap()
+    }
+
+    #[test]
+    fn test_unique_solution() {
+        let trace = example().elimination_trace().unwrap();
+
+        assert_eq!(trace.pivots(), &[(0, 0), (1, 1)]);
+        assert_eq!(trace.steps().len(), 3);
+        assert_eq!(trace.result().data(), &[1.0, 0.0, -4.0, 0.0, 1.0, 4.5]);
+        assert_eq!(trace.outcome(), SystemOutcome::Unique(vec![-4.0, 4.5]));
+
+        assert_eq!(
+            trace.log().steps(),
+            ["R2 -> R2 - 3 * R1", "R2 -> -0.5 * R2", "R1 -> R1 - 2 * R2"]
+        );
+    }
+
+    #[test]
+    fn test_row_swaps_and_special_systems() {
+        let needs_swap: Matrix<2, 3> = Matrix::new(vec![0.0, 1.0, 2.0, 1.0, 1.0, 3.0]).unwrap();
+        let trace = needs_swap.elimination_trace().unwrap();
+        assert_eq!(trace.steps()[0].operation, ElementaryOp::SwapRows(0, 1));
+        assert_eq!(trace.outcome(), SystemOutcome::Unique(vec![1.0, 2.0]));
+
+        let inconsistent: Matrix<2, 3> = Matrix::new(vec![1.0, 1.0, 1.0, 2.0, 2.0, 3.0]).unwrap();
+        assert_eq!(
+            inconsistent.elimination_trace().unwrap().outcome(),
+            SystemOutcome::Inconsistent
+        );
+
+        let underdetermined: Matrix<2, 4> =
+            Matrix::new(vec![1.0, 2.0, 1.0, 4.0, 2.0, 4.0, 3.0, 9.0]).unwrap();
+        assert_eq!(
+            underdetermined.elimination_trace().unwrap().outcome(),
+            SystemOutcome::Infinite {
+                free_variables: vec![1]
+            }
+        );
+
+        // The tolerance scales with the entries, so a system of tiny numbers is not mistaken for zero.
+        let tiny: Matrix<1, 2> = Matrix::new(vec![1e-13, 1e-13]).unwrap();
+        assert_eq!(
+            tiny.elimination_trace().unwrap().outcome(),
+            SystemOutcome::Unique(vec![1.0])
+        );
+
+        // Subnormal entries count as zero, as scaling a row by their reciprocal would overflow.
+        let subnormal: Matrix<1, 2> = Matrix::new(vec![1e-309, 1e-309]).unwrap();
+        assert_eq!(
+            subnormal.elimination_trace().unwrap().outcome(),
+            SystemOutcome::Infinite {
+                free_variables: vec![0]
+            }
+        );
+
+        let nan: Matrix<2, 2> = Matrix::new(vec![1.0, 2.0, f64::NAN, 1.0]).unwrap();
+        assert!(matches!(
+            nan.elimination_trace(),
+            Err(MatrixError::InvalidOperation(_))
+        ));
+
+        let zero: Matrix<2, 3> = Matrix::new(vec![0.0; 6]).unwrap();
+        assert_eq!(
+            zero.elimination_trace().unwrap().outcome(),
+            SystemOutcome::Infinite {
+                free_variables: vec![0, 1]
+            }
+        );
+    }
+
+    #[test]
+    fn test_rendering() {
+        let trace = example().elimination_trace().unwrap();
+
+        let text = trace.to_text();
+        assert!(text.starts_with("Start:\n[ 1 2 | 5 ]\n[ 3 4 | 6 ]\n"));
+        assert!(text.contains(
+            "Step 1: pivot at row 1, column 1; R2 -> R2 - 3 * R1\n[  1  2 |  5 ]\n[  0 -2 | -9 ]\n"
+        ));
+
+        let markdown = trace.to_markdown();
+        assert!(markdown.starts_with(
+            "**Start**\n\n| x1 | x2 | b |\n|---|---|---|\n| 1 | 2 | 5 |\n| 3 | 4 | 6 |\n"
+        ));
+        assert!(markdown.contains("**Step 2.** Pivot at row 2, column 2: `R2 -> -0.5 * R2`"));
+
+        let latex = trace.to_latex();
+        assert!(latex.starts_with(
+            "\\begin{align*}\n&\\begin{bmatrix} 1 & 2 & 5 \\\\ 3 & 4 & 6 \\end{bmatrix}\n"
+        ));
+        assert!(latex.contains("\\xrightarrow{R_{2} \\to R_{2} - 3 R_{1}} &\\begin{bmatrix} 1 & 2 & 5 \\\\ 0 & -2 & -9 \\end{bmatrix}"));
+        assert!(latex.ends_with("\\end{align*}\n"));
+    }
+}
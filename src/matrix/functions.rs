@@ -0,0 +1,294 @@
+//! Functions of square matrices: integer powers, the exponential, the square root and the logarithm.
+
+use crate::{error::MatrixError, matrix::Matrix};
+
+use super::{
+    lu::Lu,
+    norm::{max_norm, one_norm},
+};
+
+/// The maximum number of iterations of the square root iteration.
+const MAX_SQRT_ITERATIONS: usize = 100;
+/// The maximum number of square roots taken before the logarithm series is used.
+const MAX_LN_SQUARE_ROOTS: i32 = 64;
+/// The degree of the Padé approximant used by the exponential.
+const PADE_DEGREE: usize = 6;
+/// The most squarings the exponential does. Any finite norm needs fewer, as it is below `2^1024`.
+const MAX_EXP_SQUARINGS: i32 = 1100;
+
+impl<const N: usize> Matrix<N, N> {
+    /// Raises the matrix to the integer power `n` by repeated squaring.
+    ///
+    /// Negative powers are powers of the inverse, so they return a `Singular` error if the matrix has no
+    /// inverse. The zeroth power is the identity matrix.
+    pub fn pow(&self, n: i32) -> Result<Matrix<N, N>, MatrixError> {
+        let mut base = if n < 0 { self.inverse()? } else { self.clone() };
+
+        let mut result: Matrix<N, N> = Matrix::new_identity_matrix(N);
+        let mut exponent = n.unsigned_abs();
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.multiply(&base);
+            }
+
+            exponent >>= 1;
+
+            if exponent > 0 {
+                base = base.multiply(&base);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the matrix exponential `e^A`, computed with a Padé approximation and scaling and squaring.
+    ///
+    /// A matrix with infinite or NaN entries gives a matrix of NaN.
+    pub fn exp(&self) -> Matrix<N, N> {
+        // Scale the matrix so that its norm is at most 1/2, where the approximation is accurate.
+        let norm = one_norm(N, N, &self.matrix);
+        if !norm.is_finite() {
+            return Matrix {
+                matrix: vec![f64::NAN; N * N],
+            };
+        }
+
+        let squarings = if norm > 0.5 {
+            ((norm / 0.5).log2().ceil() as i32).min(MAX_EXP_SQUARINGS)
+        } else {
+            0
+        };
+
+        let a = self.map(|f| f * 0.5f64.powi(squarings));
+
+        // Evaluate the numerator and denominator of the diagonal Padé approximant together.
+        let mut numerator = identity(N);
+        let mut denominator = identity(N);
+        let mut power: Matrix<N, N> = Matrix::new_identity_matrix(N);
+        let mut coefficient = 1.0;
+
+        for k in 1..=PADE_DEGREE {
+            coefficient *= (PADE_DEGREE - k + 1) as f64 / (k * (2 * PADE_DEGREE - k + 1)) as f64;
+            power = a.multiply(&power);
+
+            let sign = if k % 2 == 0 { 1.0 } else { -1.0 };
+            for ((numerator, denominator), power) in numerator
+                .iter_mut()
+                .zip(&mut denominator)
+                .zip(&power.matrix)
+            {
+                *numerator += coefficient * power;
+                *denominator += sign * coefficient * power;
+            }
+        }
+
+        // The denominator is close to the identity for such small norms, so it is always invertible.
+        Lu::<N>::new(&denominator).solve_in_place(&mut numerator, N);
+
+        let mut result = Matrix { matrix: numerator };
+        for _ in 0..squarings {
+            result = result.multiply(&result);
+        }
+
+        result
+    }
+
+    /// Returns the principal square root of the matrix, computed with the Denman–Beavers iteration.
+    ///
+    /// The matrix must not have eigenvalues on the closed negative real axis. A `Singular` error is
+    /// returned if it is singular, and a `NoConvergence` error if the iteration does not settle.
+    pub fn sqrt(&self) -> Result<Matrix<N, N>, MatrixError> {
+        let matrix = sqrt::<N>(&self.matrix)?;
+
+        Ok(Matrix { matrix })
+    }
+
+    /// Returns the principal logarithm of the matrix, computed with inverse scaling and squaring.
+    ///
+    /// The matrix must not have eigenvalues on the closed negative real axis. A `Singular` error is
+    /// returned if it is singular, and a `NoConvergence` error if it cannot be brought close enough to
+    /// the identity.
+    pub fn ln(&self) -> Result<Matrix<N, N>, MatrixError> {
+        let mut a = self.matrix.clone();
+        let mut square_roots = 0;
+
+        // Take square roots until the matrix is close to the identity, where the series converges fast.
+        loop {
+            let x = sub_identity(N, &a);
+
+            if one_norm(N, N, &x) <= 0.25 {
+                a = x;
+                break;
+            }
+
+            if square_roots == MAX_LN_SQUARE_ROOTS {
+                return Err(MatrixError::NoConvergence);
+            }
+
+            a = sqrt::<N>(&a)?;
+            square_roots += 1;
+        }
+
+        // log(I + X) = X - X^2 / 2 + X^3 / 3 - ...
+        let a: Matrix<N, N> = Matrix { matrix: a };
+        let mut result = vec![0.0; N * N];
+        let mut power: Matrix<N, N> = Matrix::new_identity_matrix(N);
+
+        for k in 1.. {
+            power = power.multiply(&a);
+
+            let sign = if k % 2 == 0 { -1.0 } else { 1.0 };
+            let mut largest: f64 = 0.0;
+
+            for (result, power) in result.iter_mut().zip(&power.matrix) {
+                let term = sign * power / k as f64;
+                *result += term;
+                largest = largest.max(term.abs());
+            }
+
+            if largest <= f64::EPSILON * max_norm(&result) || largest == 0.0 {
+                break;
+            }
+        }
+
+        let scale = 2f64.powi(square_roots);
+        result.iter_mut().for_each(|f| *f *= scale);
+
+        Ok(Matrix { matrix: result })
+    }
+}
+
+/// Returns the principal square root of an `N` x `N` matrix with the Denman–Beavers iteration.
+fn sqrt<const N: usize>(a: &[f64]) -> Result<Vec<f64>, MatrixError> {
+    let mut y = a.to_vec();
+    let mut z = identity(N);
+    let mut previous_change = f64::INFINITY;
+
+    for _ in 0..MAX_SQRT_ITERATIONS {
+        let y_inverse = Lu::<N>::new(&y).inverse()?;
+        let z_inverse = Lu::<N>::new(&z).inverse()?;
+
+        let next_y: Vec<f64> = y
+            .iter()
+            .zip(&z_inverse)
+            .map(|(a, b)| (a + b) / 2.0)
+            .collect();
+        z = z
+            .iter()
+            .zip(&y_inverse)
+            .map(|(a, b)| (a + b) / 2.0)
+            .collect();
+
+        let change: f64 = next_y
+            .iter()
+            .zip(&y)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0, f64::max);
+        y = next_y;
+
+        if !change.is_finite() {
+            break;
+        }
+
+        // Stop once the change is at the rounding level, or has stopped shrinking when already small.
+        let norm = max_norm(&y);
+        if change <= 4.0 * f64::EPSILON * norm
+            || (change <= 1e-8 * norm && change >= previous_change)
+        {
+            return Ok(y);
+        }
+
+        previous_change = change;
+    }
+
+    Err(MatrixError::NoConvergence)
+}
+
+fn identity(n: usize) -> Vec<f64> {
+    (0..n * n)
+        .map(|i| if i / n == i % n { 1.0 } else { 0.0 })
+        .collect()
+}
+
+fn sub_identity(n: usize, a: &[f64]) -> Vec<f64> {
+    a.iter()
+        .enumerate()
+        .map(|(i, f)| if i / n == i % n { f - 1.0 } else { *f })
+        .collect()
+}
+
+#[cfg(test)]
+mod function_tests {
+    use super::*;
+
+    fn assert_close<const N: usize>(a: &Matrix<N, N>, expected: &[f64]) {
+        for (x, y) in a.data().iter().zip(expected) {
+            assert!((x - y).abs() < 1e-10, "{:?} != {:?}", a.data(), expected);
+        }
+    }
+
+    #[test]
+    fn test_integer_powers() {
+        let fibonacci: Matrix<2, 2> = Matrix::new(vec![1.0, 1.0, 1.0, 0.0]).unwrap();
+
+        assert_eq!(fibonacci.pow(10).unwrap().data(), &[89.0, 55.0, 55.0, 34.0]);
+        assert_eq!(fibonacci.pow(0).unwrap().data(), &[1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(fibonacci.pow(-2).unwrap().data(), &[1.0, -1.0, -1.0, 2.0]);
+
+        let singular: Matrix<2, 2> = Matrix::new(vec![1.0, 2.0, 2.0, 4.0]).unwrap();
+        assert!(matches!(singular.pow(-1), Err(MatrixError::Singular)));
+    }
+
+    #[test]
+    fn test_exponential() {
+        let nilpotent: Matrix<2, 2> = Matrix::new(vec![0.0, 1.0, 0.0, 0.0]).unwrap();
+        assert_close(&nilpotent.exp(), &[1.0, 1.0, 0.0, 1.0]);
+
+        // The exponential of a rotation generator is a rotation.
+        let t = 3.0;
+        let generator: Matrix<2, 2> = Matrix::new(vec![0.0, -t, t, 0.0]).unwrap();
+        assert_close(&generator.exp(), &[t.cos(), -t.sin(), t.sin(), t.cos()]);
+
+        let diagonal: Matrix<3, 3> =
+            Matrix::new(vec![1.0, 0.0, 0.0, 0.0, -2.0, 0.0, 0.0, 0.0, 0.0]).unwrap();
+        let e = 1f64.exp();
+        assert_close(
+            &diagonal.exp(),
+            &[e, 0.0, 0.0, 0.0, (-2f64).exp(), 0.0, 0.0, 0.0, 1.0],
+        );
+    }
+
+    #[test]
+    fn test_exponential_of_non_finite_matrix() {
+        let infinite: Matrix<2, 2> = Matrix::new(vec![f64::INFINITY, 0.0, 0.0, 1.0]).unwrap();
+        assert!(infinite.exp().data().iter().all(|f| f.is_nan()));
+
+        let nan: Matrix<2, 2> = Matrix::new(vec![0.0, f64::NAN, 0.0, 0.0]).unwrap();
+        assert!(nan.exp().data().iter().all(|f| f.is_nan()));
+
+        // A huge but finite norm still finishes, overflowing like the scalar exponential.
+        let huge: Matrix<1, 1> = Matrix::new(vec![1e300]).unwrap();
+        assert_eq!(huge.exp().data(), &[f64::INFINITY]);
+    }
+
+    #[test]
+    fn test_square_root() {
+        let matrix: Matrix<2, 2> = Matrix::new(vec![33.0, 24.0, 48.0, 57.0]).unwrap();
+        assert_close(&matrix.sqrt().unwrap(), &[5.0, 2.0, 4.0, 7.0]);
+
+        let singular: Matrix<2, 2> = Matrix::new(vec![0.0, 0.0, 0.0, 1.0]).unwrap();
+        assert!(matches!(singular.sqrt(), Err(MatrixError::Singular)));
+    }
+
+    #[test]
+    fn test_logarithm() {
+        let matrix: Matrix<3, 3> =
+            Matrix::new(vec![0.5, 0.2, 0.0, -0.3, 1.0, 0.4, 0.1, 0.0, 1.5]).unwrap();
+
+        assert_close(&matrix.exp().ln().unwrap(), matrix.data());
+
+        let identity: Matrix<2, 2> = Matrix::new_identity_matrix(2);
+        assert_close(&identity.ln().unwrap(), &[0.0; 4]);
+    }
+}
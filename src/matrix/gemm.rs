@@ -0,0 +1,226 @@
+//! Cache-blocked matrix multiplication kernel.
+//!
+//! The operands are split into blocks that fit in cache, and each block of the output is computed in
+//! `MR` x `NR` tiles whose accumulators live in registers. Nothing is allocated.
+
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[cfg(feature = "parallel")]
+use crate::parallel;
+use crate::simd;
+
+/// Rows of `a` per cache block.
+const MC: usize = 64;
+/// Shared dimension per cache block.
+const KC: usize = 256;
+/// Columns of `b` per cache block.
+const NC: usize = 512;
+/// Rows per register tile.
+const MR: usize = 4;
+/// Columns per register tile.
+const NR: usize = 4;
+
+/// Computes `c = a * b` where `a` is `m` x `k`, `b` is `k` x `n` and `c` is `m` x `n`, all row-major.
+pub(crate) fn gemm(m: usize, n: usize, k: usize, a: &[f64], b: &[f64], c: &mut [f64]) {
+    debug_assert_eq!(a.len(), m * k);
+    debug_assert_eq!(b.len(), k * n);
+    debug_assert_eq!(c.len(), m * n);
+
+    // Large products are split into horizontal bands of `MC` rows, one per task.
+    #[cfg(feature = "parallel")]
+    if m > MC && n > 0 && k > 0 && parallel::should_parallelize(m * n * k) {
+        c.par_chunks_mut(MC * n)
+            .zip(a.par_chunks(MC * k))
+            .for_each(|(c, a)| gemm_serial(c.len() / n, n, k, a, b, c));
+
+        return;
+    }
+
+    gemm_serial(m, n, k, a, b, c)
+}
+
+/// Computes `c = a * b` on the calling thread.
+fn gemm_serial(m: usize, n: usize, k: usize, a: &[f64], b: &[f64], c: &mut [f64]) {
+    c.fill(0.0);
+
+    let vectorized = simd::has_avx_fma();
+
+    for jc in (0..n).step_by(NC) {
+        let nc = NC.min(n - jc);
+
+        for pc in (0..k).step_by(KC) {
+            let kc = KC.min(k - pc);
+
+            for ic in (0..m).step_by(MC) {
+                let mc = MC.min(m - ic);
+
+                for ir in (ic..ic + mc).step_by(MR) {
+                    let mr = MR.min(ic + mc - ir);
+
+                    for jr in (jc..jc + nc).step_by(NR) {
+                        let nr = NR.min(jc + nc - jr);
+
+                        let tile = Tile {
+                            row: ir,
+                            column: jr,
+                            depth: pc,
+                            kc,
+                            n,
+                            k,
+                        };
+
+                        if mr == MR && nr == NR && vectorized {
+                            // SAFETY: the required CPU features were detected above.
+                            unsafe { tile.full_avx(a, b, c) };
+                        } else if mr == MR && nr == NR {
+                            tile.full(a, b, c);
+                        } else {
+                            tile.partial(mr, nr, a, b, c);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A register tile of the output, along with the slice of the shared dimension being accumulated.
+struct Tile {
+    row: usize,
+    column: usize,
+    depth: usize,
+    kc: usize,
+    n: usize,
+    k: usize,
+}
+
+impl Tile {
+    /// Accumulates a full `MR` x `NR` tile.
+    #[inline(always)]
+    fn full(&self, a: &[f64], b: &[f64], c: &mut [f64]) {
+        let mut acc = [[0.0; NR]; MR];
+
+        for p in self.depth..self.depth + self.kc {
+            let b_row: &[f64; NR] = b[p * self.n + self.column..][..NR].try_into().unwrap();
+
+            for (r, acc_row) in acc.iter_mut().enumerate() {
+                let a_entry = a[(self.row + r) * self.k + p];
+
+                for s in 0..NR {
+                    acc_row[s] += a_entry * b_row[s];
+                }
+            }
+        }
+
+        self.store(MR, NR, &acc, c);
+    }
+
+    /// Accumulates a full `MR` x `NR` tile, holding each row of the tile in one AVX register.
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "avx,fma")]
+    unsafe fn full_avx(&self, a: &[f64], b: &[f64], c: &mut [f64]) {
+        let mut acc = [_mm256_setzero_pd(); MR];
+
+        for p in self.depth..self.depth + self.kc {
+            let b_row = &b[p * self.n + self.column..][..NR];
+            let b_row = _mm256_loadu_pd(b_row.as_ptr());
+
+            for (r, acc_row) in acc.iter_mut().enumerate() {
+                let a_entry = _mm256_set1_pd(a[(self.row + r) * self.k + p]);
+
+                *acc_row = _mm256_fmadd_pd(a_entry, b_row, *acc_row);
+            }
+        }
+
+        let mut tile = [[0.0; NR]; MR];
+        for (row, acc_row) in tile.iter_mut().zip(acc) {
+            _mm256_storeu_pd(row.as_mut_ptr(), acc_row);
+        }
+
+        self.store(MR, NR, &tile, c);
+    }
+
+    /// Accumulates an `mr` x `nr` tile on the edge of the output.
+    fn partial(&self, mr: usize, nr: usize, a: &[f64], b: &[f64], c: &mut [f64]) {
+        let mut acc = [[0.0; NR]; MR];
+
+        for p in self.depth..self.depth + self.kc {
+            for (r, acc_row) in acc.iter_mut().enumerate().take(mr) {
+                let a_entry = a[(self.row + r) * self.k + p];
+
+                for s in 0..nr {
+                    acc_row[s] += a_entry * b[p * self.n + self.column + s];
+                }
+            }
+        }
+
+        self.store(mr, nr, &acc, c);
+    }
+
+    /// Adds the accumulated tile into the output.
+    #[inline(always)]
+    fn store(&self, mr: usize, nr: usize, acc: &[[f64; NR]; MR], c: &mut [f64]) {
+        for (r, acc_row) in acc.iter().enumerate().take(mr) {
+            let start = (self.row + r) * self.n + self.column;
+
+            c[start..start + nr]
+                .iter_mut()
+                .zip(acc_row)
+                .for_each(|(c, acc)| *c += acc);
+        }
+    }
+}
+
+#[cfg(test)]
+mod gemm_tests {
+    use super::*;
+
+    fn naive(m: usize, n: usize, k: usize, a: &[f64], b: &[f64]) -> Vec<f64> {
+        (0..m * n)
+            .map(|i| (0..k).map(|p| a[(i / n) * k + p] * b[p * n + i % n]).sum())
+            .collect()
+    }
+
+    #[test]
+    fn test_against_naive_multiplication() {
+        // Shapes around the tile and block boundaries, including ones larger than a block.
+        let shapes = [
+            (1, 1, 1),
+            (4, 4, 4),
+            (5, 3, 7),
+            (3, 9, 2),
+            (13, 17, 11),
+            (70, 5, 300),
+            (2, 600, 3),
+            (65, 66, 257),
+        ];
+
+        for (m, n, k) in shapes {
+            // Small integers so the results are exact regardless of summation order.
+            let a: Vec<f64> = (0..m * k)
+                .map(|i| ((i * 7 + 3) % 11) as f64 - 5.0)
+                .collect();
+            let b: Vec<f64> = (0..k * n)
+                .map(|i| ((i * 5 + 1) % 13) as f64 - 6.0)
+                .collect();
+
+            let mut c = vec![f64::NAN; m * n];
+            gemm(m, n, k, &a, &b, &mut c);
+
+            assert_eq!(c, naive(m, n, k, &a, &b), "shape {:?}", (m, n, k));
+        }
+    }
+
+    #[test]
+    fn test_empty_shared_dimension() {
+        let mut c = vec![1.0; 6];
+
+        gemm(2, 3, 0, &[], &[], &mut c);
+
+        assert_eq!(c, [0.0; 6]);
+    }
+}
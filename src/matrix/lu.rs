@@ -0,0 +1,278 @@
+//! LU decomposition with partial pivoting, used to invert square matrices and solve systems with them.
+
+use crate::{
+    error::MatrixError,
+    matrix::{permutation::Permutation, Matrix},
+    parallel,
+};
+
+/// The factorization `PA = LU` of an `N` x `N` matrix, with `L` and `U` stored in one buffer.
+pub(crate) struct Lu<const N: usize> {
+    /// `U` on and above the diagonal, and the multipliers of the unit lower triangular `L` below it.
+    lu: Vec<f64>,
+    /// The row permutation `P` picked by the pivoting.
+    permutation: Permutation<N>,
+    singular: bool,
+}
+
+impl<const N: usize> Lu<N> {
+    /// Factors the `N` x `N` row-major matrix `data`.
+    pub(crate) fn new(data: &[f64]) -> Self {
+        let mut lu = data.to_vec();
+        let mut permutation = Permutation::identity();
+        let mut singular = false;
+
+        for k in 0..N {
+            // Pick the largest entry of the column as the pivot to keep the multipliers small.
+            let pivot = (k..N)
+                .max_by(|&a, &b| lu[a * N + k].abs().total_cmp(&lu[b * N + k].abs()))
+                .unwrap();
+
+            if pivot != k {
+                permutation.swap(k, pivot).unwrap();
+                for j in 0..N {
+                    lu.swap(k * N + j, pivot * N + j);
+                }
+            }
+
+            let diagonal = lu[k * N + k];
+            if diagonal == 0.0 {
+                singular = true;
+                continue;
+            }
+
+            // The rows below the pivot are updated independently, so they can be split across threads.
+            let (above, below) = lu.split_at_mut((k + 1) * N);
+            let pivot_row = &above[k * N..];
+            parallel::rows(below, N, (N - k) * (N - k), |row| {
+                let multiplier = row[k] / diagonal;
+                row[k] = multiplier;
+
+                row[k + 1..]
+                    .iter_mut()
+                    .zip(&pivot_row[k + 1..])
+                    .for_each(|(entry, pivot)| *entry -= multiplier * pivot);
+            });
+        }
+
+        Self {
+            lu,
+            permutation,
+            singular,
+        }
+    }
+
+    /// Checks whether a zero pivot was found, in which case the matrix has no inverse.
+    pub(crate) fn is_singular(&self) -> bool {
+        self.singular
+    }
+
+    /// Overwrites the `N` x `m` row-major matrix `b` with the solution `X` of `AX = b`.
+    ///
+    /// The factored matrix must not be singular.
+    pub(crate) fn solve_in_place(&self, b: &mut [f64], m: usize) {
+        debug_assert_eq!(b.len(), N * m);
+
+        // Apply `P` to the rows of `b`.
+        let permuted: Vec<f64> = self
+            .permutation
+            .indices()
+            .iter()
+            .flat_map(|&i| &b[i * m..(i + 1) * m])
+            .copied()
+            .collect();
+        b.copy_from_slice(&permuted);
+
+        // Forward substitution with the unit lower triangular factor.
+        for i in 0..N {
+            for k in 0..i {
+                let multiplier = self.lu[i * N + k];
+
+                for j in 0..m {
+                    b[i * m + j] -= multiplier * b[k * m + j];
+                }
+            }
+        }
+
+        // Back substitution with the upper triangular factor.
+        for i in (0..N).rev() {
+            for k in i + 1..N {
+                let entry = self.lu[i * N + k];
+
+                for j in 0..m {
+                    b[i * m + j] -= entry * b[k * m + j];
+                }
+            }
+
+            let diagonal = self.lu[i * N + i];
+            b[i * m..(i + 1) * m]
+                .iter_mut()
+                .for_each(|f| *f /= diagonal);
+        }
+    }
+
+    /// Overwrites the `N` x `m` row-major matrix `b` with the solution `X` of `AᵀX = b`, reusing the
+    /// factors of `A`.
+    ///
+    /// The factored matrix must not be singular.
+    pub(crate) fn solve_transpose_in_place(&self, b: &mut [f64], m: usize) {
+        debug_assert_eq!(b.len(), N * m);
+
+        // Forward substitution with `Uᵀ`, which is lower triangular.
+        for i in 0..N {
+            for k in 0..i {
+                let entry = self.lu[k * N + i];
+
+                for j in 0..m {
+                    b[i * m + j] -= entry * b[k * m + j];
+                }
+            }
+
+            let diagonal = self.lu[i * N + i];
+            b[i * m..(i + 1) * m]
+                .iter_mut()
+                .for_each(|f| *f /= diagonal);
+        }
+
+        // Back substitution with `Lᵀ`, which is unit upper triangular.
+        for i in (0..N).rev() {
+            for k in i + 1..N {
+                let multiplier = self.lu[k * N + i];
+
+                for j in 0..m {
+                    b[i * m + j] -= multiplier * b[k * m + j];
+                }
+            }
+        }
+
+        // This solved for `Pb`, so undo the permutation.
+        let mut unpermuted = vec![0.0; N * m];
+        for (i, &row) in self.permutation.indices().iter().enumerate() {
+            unpermuted[row * m..(row + 1) * m].copy_from_slice(&b[i * m..(i + 1) * m]);
+        }
+        b.copy_from_slice(&unpermuted);
+    }
+
+    /// Returns the inverse of the factored matrix, or a `Singular` error if it has none.
+    pub(crate) fn inverse(&self) -> Result<Vec<f64>, MatrixError> {
+        if self.is_singular() {
+            return Err(MatrixError::Singular);
+        }
+
+        let mut inverse: Vec<f64> = (0..N * N)
+            .map(|i| if i / N == i % N { 1.0 } else { 0.0 })
+            .collect();
+
+        self.solve_in_place(&mut inverse, N);
+
+        Ok(inverse)
+    }
+}
+
+impl<const N: usize> Matrix<N, N> {
+    /// Returns the inverse of the matrix, computed through an LU decomposition with partial pivoting.
+    ///
+    /// Returns a `Singular` error if the matrix has no inverse.
+    pub fn inverse(&self) -> Result<Matrix<N, N>, MatrixError> {
+        let matrix = Lu::<N>::new(&self.matrix).inverse()?;
+
+        Ok(Matrix { matrix })
+    }
+}
+
+#[cfg(test)]
+mod lu_tests {
+    use super::*;
+
+    #[test]
+    fn test_inverse() {
+        let matrix: Matrix<3, 3> =
+            Matrix::new(vec![0.0, 2.0, 1.0, 1.0, 1.0, 0.0, 3.0, 0.0, 1.0]).unwrap();
+
+        let inverse = matrix.inverse().unwrap();
+        let identity = matrix.multiply(&inverse);
+
+        for (i, f) in identity.data().iter().enumerate() {
+            let expected = if i % 4 == 0 { 1.0 } else { 0.0 };
+            assert!((f - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_factorization() {
+        let matrix: Matrix<3, 3> =
+            Matrix::new(vec![0.0, 2.0, 1.0, 1.0, 1.0, 0.0, 3.0, 0.0, 1.0]).unwrap();
+        let lu = Lu::<3>::new(matrix.data());
+
+        assert_eq!(lu.permutation.indices(), &[2, 0, 1]);
+
+        let lower = (0..9).map(|i| match (i / 3, i % 3) {
+            (i, j) if i == j => 1.0,
+            (i, j) if i > j => lu.lu[i * 3 + j],
+            _ => 0.0,
+        });
+        let upper = (0..9).map(|i| if i / 3 <= i % 3 { lu.lu[i] } else { 0.0 });
+        let lower: Matrix<3, 3> = Matrix::new(lower.collect()).unwrap();
+        let upper: Matrix<3, 3> = Matrix::new(upper.collect()).unwrap();
+
+        let product = lower.multiply(&upper);
+        let permuted = lu.permutation.permute_rows(&matrix);
+        for (a, b) in product.data().iter().zip(permuted.data()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_solve_transpose() {
+        let matrix: Matrix<3, 3> =
+            Matrix::new(vec![0.0, 2.0, 1.0, 1.0, 1.0, 0.0, 3.0, 0.0, 1.0]).unwrap();
+        let lu = Lu::<3>::new(matrix.data());
+
+        let mut b = [1.0, -2.0, 4.0];
+        lu.solve_transpose_in_place(&mut b, 1);
+
+        let check = matrix
+            .transpose()
+            .multiply(&Matrix::<3, 1>::new(b.to_vec()).unwrap());
+        for (a, b) in check.data().iter().zip([1.0, -2.0, 4.0]) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_large_inverse() {
+        // Large enough for the row updates to be split across threads with the `parallel` feature.
+        const N: usize = 200;
+        let data = (0..N * N)
+            .map(|i| match (i / N, i % N) {
+                (i, j) if i == j => N as f64,
+                (i, j) => ((i * 7 + j * 3) % 11) as f64 - 5.0,
+            })
+            .collect();
+        let matrix: Matrix<N, N> = Matrix::new(data).unwrap();
+
+        let identity = matrix.multiply(&matrix.inverse().unwrap());
+        for (i, f) in identity.data().iter().enumerate() {
+            let expected = if i % (N + 1) == 0 { 1.0 } else { 0.0 };
+            assert!((f - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_singular_matrix() {
+        let matrix: Matrix<3, 3> =
+            Matrix::new(vec![1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 1.0, 0.0, 1.0]).unwrap();
+
+        assert!(matches!(matrix.inverse(), Err(MatrixError::Singular)));
+    }
+
+    #[test]
+    fn test_solve() {
+        let lu = Lu::<2>::new(&[2.0, 1.0, 4.0, 5.0]);
+        let mut b = [3.0, 1.0, 9.0, 5.0];
+
+        lu.solve_in_place(&mut b, 2);
+
+        assert_eq!(b, [1.0, 0.0, 1.0, 1.0]);
+    }
+}
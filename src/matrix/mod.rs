@@ -1,13 +1,29 @@
 //! Matrices
 
+pub mod banded;
+pub mod block;
+pub mod cofactor;
+pub mod diagonal;
+pub mod echelon;
+pub mod elementary;
+pub mod elimination;
+pub mod functions;
+mod gemm;
+mod lu;
 pub mod mtuple;
+pub mod norm;
 pub mod ops;
 pub mod ops2x2;
+pub mod permutation;
 pub mod transform;
+pub mod reduce;
 pub mod rw;
+pub mod strassen;
+mod svd;
+pub mod triangular;
 use crate::error::MatrixError;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 /// An `R` x `J` matrix struct
 pub struct Matrix<const R: usize, const C: usize> {
     matrix: Vec<f64>,
@@ -0,0 +1,168 @@
+//! Matrix norms and condition numbers.
+
+use crate::{error::MatrixError, matrix::Matrix, simd};
+
+use super::lu::Lu;
+
+/// The maximum number of iterations of the 1-norm estimator.
+const MAX_ESTIMATOR_ITERATIONS: usize = 5;
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    /// Returns the Frobenius norm, the square root of the sum of the squares of all entries.
+    pub fn norm_frobenius(&self) -> f64 {
+        simd::norm(&self.matrix)
+    }
+
+    /// Returns the 1-norm, the largest absolute column sum.
+    pub fn norm_1(&self) -> f64 {
+        one_norm(R, C, &self.matrix)
+    }
+
+    /// Returns the ∞-norm, the largest absolute row sum.
+    pub fn norm_inf(&self) -> f64 {
+        self.matrix
+            .chunks(C.max(1))
+            .map(|row| row.iter().map(|f| f.abs()).sum::<f64>())
+            .fold(0.0, f64::max)
+    }
+
+    /// Returns the largest absolute entry.
+    pub fn norm_max(&self) -> f64 {
+        max_norm(&self.matrix)
+    }
+
+    /// Returns the spectral norm (2-norm), the largest singular value.
+    ///
+    /// Returns a `NoConvergence` error if the singular value decomposition does not settle.
+    pub fn norm_2(&self) -> Result<f64, MatrixError> {
+        Ok(self.singular_values()?.first().copied().unwrap_or(0.0))
+    }
+}
+
+impl<const N: usize> Matrix<N, N> {
+    /// Returns the condition number `||A||₂ ||A⁻¹||₂`, the ratio of the largest to the smallest singular
+    /// value, or infinity if the matrix is singular.
+    ///
+    /// Returns a `NoConvergence` error if the singular value decomposition does not settle.
+    pub fn condition_number(&self) -> Result<f64, MatrixError> {
+        let sigma = self.singular_values()?;
+
+        Ok(match (sigma.first(), sigma.last()) {
+            (Some(&largest), Some(&smallest)) if smallest > 0.0 => largest / smallest,
+            (Some(_), _) => f64::INFINITY,
+            (None, _) => 0.0,
+        })
+    }
+
+    /// Returns an estimate of the 1-norm condition number `||A||₁ ||A⁻¹||₁` without forming the inverse,
+    /// using Hager's estimator. The estimate never exceeds the true value and is usually exact.
+    ///
+    /// Returns infinity if the matrix is singular.
+    pub fn condition_estimate_1(&self) -> f64 {
+        let lu = Lu::<N>::new(&self.matrix);
+        if lu.is_singular() || N == 0 {
+            return f64::INFINITY;
+        }
+
+        let mut x = vec![1.0 / N as f64; N];
+        let mut estimate = 0.0;
+
+        for _ in 0..MAX_ESTIMATOR_ITERATIONS {
+            // y = A⁻¹x
+            let mut y = x.clone();
+            lu.solve_in_place(&mut y, 1);
+            estimate = y.iter().map(|f| f.abs()).sum();
+
+            // z = A⁻ᵀ sign(y)
+            let mut z: Vec<f64> = y
+                .iter()
+                .map(|f| if *f >= 0.0 { 1.0 } else { -1.0 })
+                .collect();
+            lu.solve_transpose_in_place(&mut z, 1);
+
+            let (j, largest) =
+                z.iter()
+                    .map(|f| f.abs())
+                    .enumerate()
+                    .fold(
+                        (0, 0.0),
+                        |best, (j, f)| if f > best.1 { (j, f) } else { best },
+                    );
+            let current = simd::dot(&z, &x);
+
+            if largest <= current {
+                break;
+            }
+
+            x = vec![0.0; N];
+            x[j] = 1.0;
+        }
+
+        self.norm_1() * estimate
+    }
+}
+
+/// The largest absolute column sum of an `rows` x `columns` row-major matrix.
+pub(crate) fn one_norm(rows: usize, columns: usize, data: &[f64]) -> f64 {
+    (0..columns)
+        .map(|j| (0..rows).map(|i| data[i * columns + j].abs()).sum::<f64>())
+        .fold(0.0, f64::max)
+}
+
+/// The largest absolute entry.
+pub(crate) fn max_norm(data: &[f64]) -> f64 {
+    data.iter().map(|f| f.abs()).fold(0.0, f64::max)
+}
+
+#[cfg(test)]
+mod norm_tests {
+    use super::*;
+
+    #[test]
+    fn test_entrywise_norms() {
+        let matrix: Matrix<2, 3> = Matrix::new(vec![1.0, -2.0, 3.0, -4.0, 5.0, -6.0]).unwrap();
+
+        assert_eq!(matrix.norm_frobenius(), 91f64.sqrt());
+        assert_eq!(matrix.norm_1(), 9.0);
+        assert_eq!(matrix.norm_inf(), 15.0);
+        assert_eq!(matrix.norm_max(), 6.0);
+    }
+
+    #[test]
+    fn test_spectral_norm() {
+        let diagonal: Matrix<2, 2> = Matrix::new(vec![3.0, 0.0, 0.0, -5.0]).unwrap();
+        assert!((diagonal.norm_2().unwrap() - 5.0).abs() < 1e-12);
+
+        // The singular values of this matrix are 5 and 3.
+        let matrix: Matrix<2, 3> = Matrix::new(vec![3.0, 2.0, 2.0, 2.0, 3.0, -2.0]).unwrap();
+        assert!((matrix.norm_2().unwrap() - 5.0).abs() < 1e-12);
+
+        // The ones vector is in the null space of this matrix.
+        let matrix: Matrix<2, 2> = Matrix::new(vec![1.0, -1.0, 1.0, -1.0]).unwrap();
+        assert!((matrix.norm_2().unwrap() - 2.0).abs() < 1e-12);
+
+        // Equal singular values, where power iteration stalls.
+        let rotation: Matrix<2, 2> = Matrix::new(vec![0.6, -0.8, 0.8, 0.6]).unwrap();
+        assert!((rotation.norm_2().unwrap() - 1.0).abs() < 1e-12);
+        assert!((rotation.condition_number().unwrap() - 1.0).abs() < 1e-12);
+
+        let zero: Matrix<2, 2> = Matrix::new(vec![0.0; 4]).unwrap();
+        assert_eq!(zero.norm_2().unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_condition_numbers() {
+        let matrix: Matrix<2, 2> = Matrix::new(vec![1.0, 0.0, 0.0, 1e-3]).unwrap();
+        assert!((matrix.condition_number().unwrap() - 1e3).abs() < 1e-6);
+
+        let matrix: Matrix<3, 3> =
+            Matrix::new(vec![4.0, 1.0, -2.0, 1.0, 3.0, 0.5, 2.0, -1.0, 5.0]).unwrap();
+        let exact = matrix.norm_1() * matrix.inverse().unwrap().norm_1();
+        let estimate = matrix.condition_estimate_1();
+        assert!(estimate <= exact * (1.0 + 1e-12) && estimate >= exact / 3.0);
+
+        let singular: Matrix<2, 2> = Matrix::new(vec![1.0, 2.0, 2.0, 4.0]).unwrap();
+        assert_eq!(singular.condition_number().unwrap(), f64::INFINITY);
+        assert_eq!(singular.condition_estimate_1(), f64::INFINITY);
+    }
+}
@@ -1,42 +1,119 @@
 //! Operations such as matrix addition, subtraction, multiplication, scalar multiplication, etc.
 
-use crate::{error::MatrixError, matrix::Matrix, vector::VectorN};
+use crate::{error::MatrixError, matrix::Matrix, parallel, simd};
 
-use super::mtuple::Direction;
+use super::gemm::gemm;
 
 impl<const R: usize, const C: usize> Matrix<R, C> {
     /// Multiplies the matrix by a scalar `a`
     pub fn scalar_multiply(&mut self, a: f64) {
-        let matrix_prime: Vec<f64> = self.matrix.iter_mut().map(|f| *f * a).collect();
-
-        self.matrix = matrix_prime
+        parallel::unary(&mut self.matrix, |chunk| simd::scale(chunk, a));
     }
 
     /// Performs matrix multiplication on this matrix and the matrix given in the `other` parameter.
     pub fn multiply<const K: usize>(&self, other: &Matrix<C, K>) -> Matrix<R, K> {
-        // Get all the horizontal tuples in this matrix
-        let horizontal_tuples: Vec<VectorN<C>> = (0..R)
-            .map(|i| VectorN::new(self.get_tuple(i, Direction::Row).try_into().unwrap()))
-            .collect();
+        let mut product = Matrix {
+            matrix: vec![0.0; R * K],
+        };
+
+        self.multiply_into(other, &mut product);
+
+        product
+    }
 
-        // Get all the vertical tuples in the other matrix
-        let vertical_tuples: Vec<VectorN<C>> = (0..C)
-            .map(|i| VectorN::new(other.get_tuple(i, Direction::Column).try_into().unwrap()))
+    /// Performs matrix multiplication on this matrix and `other`, writing the product into `out` instead of allocating a new matrix.
+    pub fn multiply_into<const K: usize>(&self, other: &Matrix<C, K>, out: &mut Matrix<R, K>) {
+        gemm(R, K, C, &self.matrix, &other.matrix, &mut out.matrix);
+    }
+
+    /// Returns the transpose, the `C` x `R` matrix whose rows are the columns of this one.
+    pub fn transpose(&self) -> Matrix<C, R> {
+        let matrix = (0..R * C)
+            .map(|index| self.matrix[(index % R) * C + index / R])
             .collect();
 
-        // Loop to calculate and set the dot product of each tuple.
-        let dotproducts: Vec<f64> = (0..(R * K))
-            .map(|i| {
-                let x = i % K;
-                let y = i / C;
+        Matrix { matrix }
+    }
+
+    /// Returns the Kronecker product of this matrix and `other`, which replaces every entry `a` of this
+    /// matrix with the block `a * other`.
+    ///
+    /// The output has to be `R * R2` x `C * C2`, otherwise a `ShapeMismatch` error is returned.
+    pub fn kronecker<const R2: usize, const C2: usize, const R3: usize, const C3: usize>(
+        &self,
+        other: &Matrix<R2, C2>,
+    ) -> Result<Matrix<R3, C3>, MatrixError> {
+        if (R3, C3) != (R * R2, C * C2) {
+            return Err(MatrixError::ShapeMismatch {
+                expected: (R * R2, C * C2),
+                found: (R3, C3),
+            });
+        }
+
+        let matrix = (0..R3 * C3)
+            .map(|index| {
+                let (i, j) = (index / C3, index % C3);
 
-                horizontal_tuples[y]
-                    .dotproduct(&vertical_tuples[x])
-                    .unwrap()
+                self.matrix[(i / R2) * C + j / C2] * other.matrix[(i % R2) * C2 + j % C2]
             })
             .collect();
 
-        Matrix::new(dotproducts).unwrap()
+        Ok(Matrix { matrix })
+    }
+
+    /// Returns the element-wise (Hadamard) product of this matrix and `other`.
+    pub fn hadamard(&self, other: &Matrix<R, C>) -> Matrix<R, C> {
+        self.zip_map(other, |a, b| a * b)
+    }
+
+    /// Divides this matrix by `other` element-wise. Dividing by zero gives infinities or NaN as usual.
+    pub fn hadamard_div(&self, other: &Matrix<R, C>) -> Matrix<R, C> {
+        self.zip_map(other, |a, b| a / b)
+    }
+
+    /// Returns a new matrix with `f` applied to every entry.
+    pub fn map(&self, f: impl FnMut(f64) -> f64) -> Matrix<R, C> {
+        let matrix = self.matrix.iter().copied().map(f).collect();
+
+        Matrix { matrix }
+    }
+
+    /// Calls `f` with a mutable reference to every entry, in row-major order.
+    pub fn map_mut(&mut self, f: impl FnMut(&mut f64)) {
+        self.matrix.iter_mut().for_each(f);
+    }
+
+    /// Replaces every entry `x` with `f(x)` in place.
+    pub fn apply(&mut self, mut f: impl FnMut(f64) -> f64) {
+        self.map_mut(|x| *x = f(*x));
+    }
+
+    /// Returns a new matrix whose entries are `f(a, b)` for the matching entries of this matrix and `other`.
+    pub fn zip_map(
+        &self,
+        other: &Matrix<R, C>,
+        mut f: impl FnMut(f64, f64) -> f64,
+    ) -> Matrix<R, C> {
+        let matrix = self
+            .matrix
+            .iter()
+            .zip(&other.matrix)
+            .map(|(&a, &b)| f(a, b))
+            .collect();
+
+        Matrix { matrix }
+    }
+
+    /// Returns a new matrix with `f(i, j, x)` applied to the entry `x` at row `i` and column `j`.
+    pub fn map_indexed(&self, mut f: impl FnMut(usize, usize, f64) -> f64) -> Matrix<R, C> {
+        let matrix = self
+            .matrix
+            .iter()
+            .enumerate()
+            .map(|(index, &x)| f(index / C, index % C, x))
+            .collect();
+
+        Matrix { matrix }
     }
 }
 
@@ -50,9 +127,8 @@ impl<const R: usize, const C: usize> std::ops::Add for Matrix<R, C> {
         let other_data = rhs.matrix;
 
         // Add each of the entries together and write the result to the corresponding entry in the new vector.
-        let new_matrix_data: Vec<f64> = (0..this_data.len())
-            .map(|i| this_data[i] + other_data[i])
-            .collect();
+        let mut new_matrix_data: Vec<f64> = vec![0.0; this_data.len()];
+        parallel::binary(&this_data, &other_data, &mut new_matrix_data, simd::add);
 
         // Make sure that the matrix construction goes as planned.
         match Matrix::new(new_matrix_data) {
@@ -72,9 +148,8 @@ impl<const R: usize, const C: usize> std::ops::Sub for Matrix<R, C> {
         let other_data = rhs.matrix;
 
         // Subtract each of the entries together and write the result to the corresponding entry in the new vector.
-        let new_matrix_data: Vec<f64> = (0..this_data.len())
-            .map(|i| this_data[i] - other_data[i])
-            .collect();
+        let mut new_matrix_data: Vec<f64> = vec![0.0; this_data.len()];
+        parallel::binary(&this_data, &other_data, &mut new_matrix_data, simd::sub);
 
         // Make sure that the matrix construction goes as planned.
         match Matrix::new(new_matrix_data) {
@@ -176,6 +251,85 @@ mod matrixop_tests {
         assert_eq!(c.data(), &[17.0, 12.0, 39.0, 26.0, 89.0, 59.0]);
     }
 
+    #[test]
+    fn test_matrix_multiplication_non_square() {
+        let data_a = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let data_b = vec![1.0, 0.0, 2.0, -1.0, 3.0, 1.0, 0.0, 2.0, 1.0, 1.0, 1.0, 1.0];
+
+        let a: Matrix<2, 3> = Matrix::new(data_a).unwrap();
+        let b: Matrix<3, 4> = Matrix::new(data_b).unwrap();
+
+        let c: Matrix<2, 4> = a.multiply(&b);
+
+        assert_eq!(c.data(), &[10.0, 5.0, 5.0, 6.0, 25.0, 11.0, 14.0, 12.0]);
+
+        let d: Matrix<1, 3> = Matrix::new(vec![1.0, 2.0, 3.0]).unwrap();
+        let e: Matrix<3, 1> = Matrix::new(vec![4.0, 5.0, 6.0]).unwrap();
+
+        assert_eq!(d.multiply(&e).data(), &[32.0]);
+        assert_eq!(e.multiply(&d).data(), &[4.0, 8.0, 12.0, 5.0, 10.0, 15.0, 6.0, 12.0, 18.0]);
+    }
+
+    #[test]
+    fn test_matrix_multiplication_into() {
+        let a: Matrix<2, 3> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+        let b: Matrix<3, 2> = Matrix::new(vec![1.0, 0.0, 0.0, 1.0, 1.0, 1.0]).unwrap();
+
+        let mut out: Matrix<2, 2> = Matrix::new(vec![9.0; 4]).unwrap();
+
+        a.multiply_into(&b, &mut out);
+        assert_eq!(out.data(), &[4.0, 5.0, 10.0, 11.0]);
+
+        // Reusing the buffer overwrites the previous product.
+        a.multiply_into(&b, &mut out);
+        assert_eq!(out.data(), &[4.0, 5.0, 10.0, 11.0]);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let a: Matrix<2, 3> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+        let transpose: Matrix<3, 2> = a.transpose();
+
+        assert_eq!(transpose.data(), &[1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+        assert_eq!(transpose.transpose(), a);
+    }
+
+    #[test]
+    fn test_kronecker_product() {
+        let a: Matrix<2, 2> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+        let b: Matrix<2, 3> = Matrix::new(vec![0.0, 5.0, 1.0, 6.0, 7.0, -1.0]).unwrap();
+
+        let c: Matrix<4, 6> = a.kronecker(&b).unwrap();
+
+        assert_eq!(
+            c.data(),
+            &[
+                0.0, 5.0, 1.0, 0.0, 10.0, 2.0, //
+                6.0, 7.0, -1.0, 12.0, 14.0, -2.0, //
+                0.0, 15.0, 3.0, 0.0, 20.0, 4.0, //
+                18.0, 21.0, -3.0, 24.0, 28.0, -4.0,
+            ]
+        );
+
+        let wrong: Result<Matrix<4, 4>, _> = a.kronecker(&b);
+        assert!(matches!(
+            wrong,
+            Err(MatrixError::ShapeMismatch {
+                expected: (4, 6),
+                found: (4, 4)
+            })
+        ));
+    }
+
+    #[test]
+    fn test_hadamard_product_and_division() {
+        let a: Matrix<2, 2> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+        let b: Matrix<2, 2> = Matrix::new(vec![2.0, 4.0, 0.5, 0.0]).unwrap();
+
+        assert_eq!(a.hadamard(&b).data(), &[2.0, 8.0, 1.5, 0.0]);
+        assert_eq!(a.hadamard_div(&b).data(), &[0.5, 0.5, 6.0, f64::INFINITY]);
+    }
+
     #[test]
     fn test_true_matrix_equivalence() {
         let data_a = vec![1.0, 2.0, 3.0, 4.0];
@@ -197,4 +351,33 @@ mod matrixop_tests {
 
         assert_ne!(a, b);
     }
+
+    #[test]
+    fn test_element_wise_combinators() {
+        let mut a: Matrix<2, 3> = Matrix::new(vec![-2.0, -1.0, 0.0, 1.0, 2.0, 3.0]).unwrap();
+        let b: Matrix<2, 3> = Matrix::new(vec![1.0; 6]).unwrap();
+
+        assert_eq!(
+            a.map(|x| x.max(0.0)).data(),
+            &[0.0, 0.0, 0.0, 1.0, 2.0, 3.0]
+        );
+        assert_eq!(
+            a.zip_map(&b, |x, y| x - y).data(),
+            &[-3.0, -2.0, -1.0, 0.0, 1.0, 2.0]
+        );
+        assert_eq!(
+            a.map_indexed(|i, j, x| if i == j { x } else { 0.0 }).data(),
+            &[-2.0, 0.0, 0.0, 0.0, 2.0, 0.0]
+        );
+
+        a.apply(|x| x.clamp(-1.0, 1.0));
+        assert_eq!(a.data(), &[-1.0, -1.0, 0.0, 1.0, 1.0, 1.0]);
+
+        let mut count = 0;
+        a.map_mut(|x| {
+            *x += count as f64;
+            count += 1;
+        });
+        assert_eq!(a.data(), &[-1.0, 0.0, 2.0, 4.0, 5.0, 6.0]);
+    }
 }
@@ -0,0 +1,215 @@
+//! Permutation matrices, stored as index arrays.
+
+use crate::{error::MatrixError, matrix::Matrix, vector::VectorN};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// An `N` x `N` permutation matrix `P`, stored as the column of the one in each row.
+///
+/// Applying it to rows moves row `indices[i]` to row `i`, which is the same as multiplying by `P` from
+/// the left.
+pub struct Permutation<const N: usize> {
+    indices: [usize; N],
+}
+
+impl<const N: usize> Permutation<N> {
+    /// Returns the identity permutation.
+    pub fn identity() -> Self {
+        Self {
+            indices: std::array::from_fn(|i| i),
+        }
+    }
+
+    /// Creates a permutation from the position each entry is taken from.
+    ///
+    /// Returns an `InvalidPermutation` error unless every index below `N` appears exactly once.
+    pub fn from_indices(indices: [usize; N]) -> Result<Self, MatrixError> {
+        let mut seen = [false; N];
+
+        for &i in &indices {
+            if i >= N || seen[i] {
+                return Err(MatrixError::InvalidPermutation);
+            }
+            seen[i] = true;
+        }
+
+        Ok(Self { indices })
+    }
+
+    /// Returns the position each entry is taken from.
+    pub fn indices(&self) -> &[usize; N] {
+        &self.indices
+    }
+
+    /// Swaps entries `i` and `j` of the permutation, which swaps rows `i` and `j` of `P`.
+    pub fn swap(&mut self, i: usize, j: usize) -> Result<(), MatrixError> {
+        if i >= N || j >= N {
+            return Err(MatrixError::UndefinedIndex);
+        }
+
+        self.indices.swap(i, j);
+
+        Ok(())
+    }
+
+    /// Returns the permutation `PQ`, which applies `other` first and then this permutation.
+    pub fn compose(&self, other: &Permutation<N>) -> Permutation<N> {
+        Self {
+            indices: std::array::from_fn(|i| other.indices[self.indices[i]]),
+        }
+    }
+
+    /// Returns the inverse permutation, which is also the transpose of `P`.
+    pub fn inverse(&self) -> Permutation<N> {
+        let mut indices = [0; N];
+
+        for (i, &j) in self.indices.iter().enumerate() {
+            indices[j] = i;
+        }
+
+        Self { indices }
+    }
+
+    /// Checks whether the permutation is made up of an even number of swaps.
+    pub fn is_even(&self) -> bool {
+        // A cycle of length `k` takes `k - 1` swaps, so the parity is that of `N` minus the cycles.
+        let mut visited = [false; N];
+        let mut cycles = 0;
+
+        for start in 0..N {
+            if visited[start] {
+                continue;
+            }
+
+            cycles += 1;
+            let mut i = start;
+            while !visited[i] {
+                visited[i] = true;
+                i = self.indices[i];
+            }
+        }
+
+        (N - cycles) & 1 == 0
+    }
+
+    /// Returns the sign of the permutation, which is also the determinant of `P`.
+    pub fn sign(&self) -> f64 {
+        if self.is_even() {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+
+    /// Returns `P` as a dense matrix.
+    pub fn to_dense(&self) -> Matrix<N, N> {
+        let mut matrix = vec![0.0; N * N];
+
+        for (i, &j) in self.indices.iter().enumerate() {
+            matrix[i * N + j] = 1.0;
+        }
+
+        Matrix { matrix }
+    }
+
+    /// Returns `PA`, whose row `i` is row `indices[i]` of `matrix`.
+    pub fn permute_rows<const C: usize>(&self, matrix: &Matrix<N, C>) -> Matrix<N, C> {
+        let matrix = self
+            .indices
+            .iter()
+            .flat_map(|&i| &matrix.matrix[i * C..(i + 1) * C])
+            .copied()
+            .collect();
+
+        Matrix { matrix }
+    }
+
+    /// Returns `APᵀ`, whose column `j` is column `indices[j]` of `matrix`.
+    pub fn permute_columns<const R: usize>(&self, matrix: &Matrix<R, N>) -> Matrix<R, N> {
+        let matrix = (0..R * N)
+            .map(|index| matrix.matrix[(index / N) * N + self.indices[index % N]])
+            .collect();
+
+        Matrix { matrix }
+    }
+
+    /// Returns `Pv`, whose entry `i` is entry `indices[i]` of `vector`.
+    pub fn permute_vector(&self, vector: &VectorN<N>) -> VectorN<N> {
+        VectorN::new(std::array::from_fn(|i| vector.data()[self.indices[i]]))
+    }
+}
+
+impl<const N: usize> Default for Permutation<N> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+#[cfg(test)]
+mod permutation_tests {
+    use super::*;
+
+    fn example() -> Permutation<3> {
+        Permutation::from_indices([2, 0, 1]).unwrap()
+    }
+
+    #[test]
+    fn test_construction() {
+        assert_eq!(Permutation::<3>::identity().indices(), &[0, 1, 2]);
+        assert!(matches!(
+            Permutation::from_indices([0, 0, 1]),
+            Err(MatrixError::InvalidPermutation)
+        ));
+        assert!(Permutation::from_indices([0, 3, 1]).is_err());
+
+        let mut permutation = Permutation::<3>::identity();
+        permutation.swap(0, 2).unwrap();
+        assert_eq!(permutation.indices(), &[2, 1, 0]);
+        assert!(permutation.swap(0, 3).is_err());
+    }
+
+    #[test]
+    fn test_application_matches_dense() {
+        let permutation = example();
+        let dense = permutation.to_dense();
+
+        let matrix: Matrix<3, 2> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+        assert_eq!(permutation.permute_rows(&matrix), dense.multiply(&matrix));
+        assert_eq!(
+            permutation.permute_rows(&matrix).data(),
+            &[5.0, 6.0, 1.0, 2.0, 3.0, 4.0]
+        );
+
+        let matrix: Matrix<2, 3> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+        let transpose = permutation.inverse().to_dense();
+        assert_eq!(
+            permutation.permute_columns(&matrix),
+            matrix.multiply(&transpose)
+        );
+
+        let vector = VectorN::new([1.0, 2.0, 3.0]);
+        assert_eq!(permutation.permute_vector(&vector).data(), &[3.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_composition_and_inverse() {
+        let p = example();
+        let q = Permutation::from_indices([1, 0, 2]).unwrap();
+
+        assert_eq!(
+            p.compose(&q).to_dense(),
+            p.to_dense().multiply(&q.to_dense())
+        );
+        assert_eq!(p.compose(&p.inverse()), Permutation::identity());
+        assert_eq!(p.inverse().compose(&p), Permutation::identity());
+    }
+
+    #[test]
+    fn test_sign() {
+        assert_eq!(Permutation::<4>::identity().sign(), 1.0);
+        assert_eq!(example().sign(), 1.0);
+
+        let swap = Permutation::from_indices([1, 0, 2]).unwrap();
+        assert_eq!(swap.sign(), -1.0);
+        assert_eq!(swap.sign(), swap.to_dense().determinant());
+    }
+}
@@ -0,0 +1,258 @@
+//! Reductions over the whole matrix or along its rows and columns, such as sums, means and extrema.
+//!
+//! Sums, products, means and variances propagate NaN like ordinary arithmetic. Minima and maxima skip
+//! NaN entries instead, giving NaN or `None` only when every entry is NaN. With the `parallel` feature,
+//! large matrices spread the rows or columns of the per-row and per-column reductions across threads.
+
+use crate::{matrix::Matrix, parallel, vector::VectorN};
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    /// Returns the sum of all entries.
+    pub fn sum(&self) -> f64 {
+        self.matrix.iter().sum()
+    }
+
+    /// Returns the product of all entries.
+    pub fn product(&self) -> f64 {
+        self.matrix.iter().product()
+    }
+
+    /// Returns the mean of all entries.
+    pub fn mean(&self) -> f64 {
+        mean(self.matrix.iter().copied())
+    }
+
+    /// Returns the population variance of all entries, the mean squared distance from their mean.
+    pub fn variance(&self) -> f64 {
+        variance(self.matrix.iter().copied())
+    }
+
+    /// Returns the smallest entry, ignoring NaN.
+    pub fn min(&self) -> f64 {
+        extreme(self.matrix.iter().copied(), f64::lt)
+    }
+
+    /// Returns the largest entry, ignoring NaN.
+    pub fn max(&self) -> f64 {
+        extreme(self.matrix.iter().copied(), f64::gt)
+    }
+
+    /// Returns the row and column of the first smallest entry, ignoring NaN.
+    pub fn argmin(&self) -> Option<(usize, usize)> {
+        arg_extreme(self.matrix.iter().copied(), f64::lt).map(|index| (index / C, index % C))
+    }
+
+    /// Returns the row and column of the first largest entry, ignoring NaN.
+    pub fn argmax(&self) -> Option<(usize, usize)> {
+        arg_extreme(self.matrix.iter().copied(), f64::gt).map(|index| (index / C, index % C))
+    }
+
+    /// Returns the sum of each row.
+    pub fn row_sums(&self) -> VectorN<R> {
+        VectorN::new(self.each_row(|i| self.row(i).sum()))
+    }
+
+    /// Returns the product of each row.
+    pub fn row_products(&self) -> VectorN<R> {
+        VectorN::new(self.each_row(|i| self.row(i).product()))
+    }
+
+    /// Returns the mean of each row.
+    pub fn row_means(&self) -> VectorN<R> {
+        VectorN::new(self.each_row(|i| mean(self.row(i))))
+    }
+
+    /// Returns the population variance of each row.
+    pub fn row_variances(&self) -> VectorN<R> {
+        VectorN::new(self.each_row(|i| variance(self.row(i))))
+    }
+
+    /// Returns the smallest entry of each row, ignoring NaN.
+    pub fn row_mins(&self) -> VectorN<R> {
+        VectorN::new(self.each_row(|i| extreme(self.row(i), f64::lt)))
+    }
+
+    /// Returns the largest entry of each row, ignoring NaN.
+    pub fn row_maxs(&self) -> VectorN<R> {
+        VectorN::new(self.each_row(|i| extreme(self.row(i), f64::gt)))
+    }
+
+    /// Returns the column of the first smallest entry of each row, ignoring NaN.
+    pub fn row_argmins(&self) -> [Option<usize>; R] {
+        self.each_row(|i| arg_extreme(self.row(i), f64::lt))
+    }
+
+    /// Returns the column of the first largest entry of each row, ignoring NaN.
+    pub fn row_argmaxs(&self) -> [Option<usize>; R] {
+        self.each_row(|i| arg_extreme(self.row(i), f64::gt))
+    }
+
+    /// Returns the sum of each column.
+    pub fn column_sums(&self) -> VectorN<C> {
+        VectorN::new(self.each_column(|j| self.column(j).sum()))
+    }
+
+    /// Returns the product of each column.
+    pub fn column_products(&self) -> VectorN<C> {
+        VectorN::new(self.each_column(|j| self.column(j).product()))
+    }
+
+    /// Returns the mean of each column.
+    pub fn column_means(&self) -> VectorN<C> {
+        VectorN::new(self.each_column(|j| mean(self.column(j))))
+    }
+
+    /// Returns the population variance of each column.
+    pub fn column_variances(&self) -> VectorN<C> {
+        VectorN::new(self.each_column(|j| variance(self.column(j))))
+    }
+
+    /// Returns the smallest entry of each column, ignoring NaN.
+    pub fn column_mins(&self) -> VectorN<C> {
+        VectorN::new(self.each_column(|j| extreme(self.column(j), f64::lt)))
+    }
+
+    /// Returns the largest entry of each column, ignoring NaN.
+    pub fn column_maxs(&self) -> VectorN<C> {
+        VectorN::new(self.each_column(|j| extreme(self.column(j), f64::gt)))
+    }
+
+    /// Returns the row of the first smallest entry of each column, ignoring NaN.
+    pub fn column_argmins(&self) -> [Option<usize>; C] {
+        self.each_column(|j| arg_extreme(self.column(j), f64::lt))
+    }
+
+    /// Returns the row of the first largest entry of each column, ignoring NaN.
+    pub fn column_argmaxs(&self) -> [Option<usize>; C] {
+        self.each_column(|j| arg_extreme(self.column(j), f64::gt))
+    }
+
+    /// Computes `f(i)` for every row `i`, splitting the rows across threads if there is enough work.
+    fn each_row<T: Copy + Send>(&self, f: impl Fn(usize) -> T + Sync + Send) -> [T; R] {
+        let rows: Vec<usize> = (0..R).collect();
+        let values = parallel::map(&rows, C, |&i| f(i));
+
+        std::array::from_fn(|i| values[i])
+    }
+
+    /// Computes `f(j)` for every column `j`, splitting the columns across threads if there is enough work.
+    fn each_column<T: Copy + Send>(&self, f: impl Fn(usize) -> T + Sync + Send) -> [T; C] {
+        let columns: Vec<usize> = (0..C).collect();
+        let values = parallel::map(&columns, R, |&j| f(j));
+
+        std::array::from_fn(|j| values[j])
+    }
+
+    fn row(&self, i: usize) -> impl Iterator<Item = f64> + Clone + '_ {
+        self.matrix[i * C..(i + 1) * C].iter().copied()
+    }
+
+    fn column(&self, j: usize) -> impl Iterator<Item = f64> + Clone + '_ {
+        (0..R).map(move |i| self.matrix[i * C + j])
+    }
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), x| (sum + x, count + 1));
+
+    sum / count as f64
+}
+
+/// Computes the variance in two passes, which avoids the cancellation of the sum of squares formula.
+fn variance(values: impl Iterator<Item = f64> + Clone) -> f64 {
+    let mean = mean(values.clone());
+
+    self::mean(values.map(|x| (x - mean) * (x - mean)))
+}
+
+/// Returns the first entry that is `better` than all others, skipping NaN, or NaN if there is none.
+fn extreme(values: impl Iterator<Item = f64> + Clone, better: fn(&f64, &f64) -> bool) -> f64 {
+    arg_extreme(values.clone(), better).map_or(f64::NAN, |index| values.clone().nth(index).unwrap())
+}
+
+/// Returns the index of the first entry that is `better` than all others, skipping NaN.
+fn arg_extreme(values: impl Iterator<Item = f64>, better: fn(&f64, &f64) -> bool) -> Option<usize> {
+    values
+        .enumerate()
+        .filter(|(_, x)| !x.is_nan())
+        .fold(None, |best, (index, x)| match best {
+            Some((_, value)) if !better(&x, &value) => best,
+            _ => Some((index, x)),
+        })
+        .map(|(index, _)| index)
+}
+
+#[cfg(test)]
+mod reduce_tests {
+    use super::*;
+
+    fn example() -> Matrix<3, 2> {
+        Matrix::new(vec![1.0, 4.0, 3.0, 2.0, 5.0, 0.0]).unwrap()
+    }
+
+    #[test]
+    fn test_whole_matrix_reductions() {
+        let matrix = example();
+
+        assert_eq!(matrix.sum(), 15.0);
+        assert_eq!(matrix.product(), 0.0);
+        assert_eq!(matrix.mean(), 2.5);
+        assert!((matrix.variance() - 17.5 / 6.0).abs() < 1e-12);
+        assert_eq!((matrix.min(), matrix.max()), (0.0, 5.0));
+        assert_eq!(matrix.argmin(), Some((2, 1)));
+        assert_eq!(matrix.argmax(), Some((2, 0)));
+    }
+
+    #[test]
+    fn test_row_and_column_reductions() {
+        let matrix = example();
+
+        assert_eq!(matrix.row_sums().data(), &[5.0, 5.0, 5.0]);
+        assert_eq!(matrix.row_products().data(), &[4.0, 6.0, 0.0]);
+        assert_eq!(matrix.row_means().data(), &[2.5, 2.5, 2.5]);
+        assert_eq!(matrix.row_variances().data(), &[2.25, 0.25, 6.25]);
+        assert_eq!(matrix.row_mins().data(), &[1.0, 2.0, 0.0]);
+        assert_eq!(matrix.row_argmaxs(), [Some(1), Some(0), Some(0)]);
+
+        assert_eq!(matrix.column_sums().data(), &[9.0, 6.0]);
+        assert_eq!(matrix.column_products().data(), &[15.0, 0.0]);
+        assert_eq!(matrix.column_means().data(), &[3.0, 2.0]);
+        assert_eq!(matrix.column_maxs().data(), &[5.0, 4.0]);
+        assert_eq!(matrix.column_argmins(), [Some(0), Some(2)]);
+
+        let variances = matrix.column_variances();
+        assert!((variances.data()[0] - 8.0 / 3.0).abs() < 1e-12);
+        assert!((variances.data()[1] - 8.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_large_matrix() {
+        // Large enough for the rows and columns to be split across threads with the `parallel` feature.
+        let matrix: Matrix<200, 300> =
+            Matrix::new((0..200 * 300).map(|i| (i % 7) as f64).collect()).unwrap();
+
+        let row_sums = matrix.row_sums();
+        let column_maxs = matrix.column_maxs();
+        for i in 0..200 {
+            let expected: f64 = (0..300).map(|j| ((i * 300 + j) % 7) as f64).sum();
+            assert_eq!(row_sums.data()[i], expected);
+        }
+        assert!(column_maxs.data().iter().all(|&f| f == 6.0));
+        assert_eq!(matrix.column_argmins()[0], Some(0));
+    }
+
+    #[test]
+    fn test_nan_handling() {
+        let matrix: Matrix<2, 2> = Matrix::new(vec![f64::NAN, 2.0, f64::NAN, f64::NAN]).unwrap();
+
+        assert!(matrix.sum().is_nan());
+        assert!(matrix.mean().is_nan());
+        assert_eq!((matrix.min(), matrix.max()), (2.0, 2.0));
+        assert_eq!(matrix.argmin(), Some((0, 1)));
+
+        assert_eq!(matrix.row_argmins(), [Some(1), None]);
+        assert_eq!(matrix.column_argmaxs(), [None, Some(0)]);
+        assert!(matrix.column_mins().data()[0].is_nan());
+        assert_eq!(matrix.column_maxs().data()[1], 2.0);
+    }
+}
@@ -1,6 +1,6 @@
 //! Reading and writing operations.
 
-use crate::error::MatrixError;
+use crate::{error::MatrixError, parallel, simd};
 
 use super::Matrix;
 
@@ -33,11 +33,12 @@ impl<const R: usize, const C: usize> Matrix<R, C> {
 
     /// Fills the matrix with the number `k`
     pub fn fill(&mut self, k: f64) {
-        self.matrix = vec![k; C * R]
+        parallel::unary(&mut self.matrix, |chunk| simd::fill(chunk, k))
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::approx_constant)]
 mod matrix_rw {
     use super::*;
 
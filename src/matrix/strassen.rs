@@ -0,0 +1,183 @@
+//! Strassen-Winograd multiplication for large square matrices.
+//!
+//! Each level of recursion replaces the eight block products of the ordinary algorithm by seven, at the
+//! cost of a few extra additions. Below the cutoff the blocked kernel is faster, so the recursion stops
+//! there. Matrices of odd size are padded with a row and column of zeros at the level where they occur.
+
+use crate::{matrix::Matrix, simd};
+
+use super::gemm::gemm;
+
+/// The default size at or below which [`Matrix::multiply_strassen`] falls back to the blocked kernel.
+pub const DEFAULT_CUTOFF: usize = 128;
+
+impl<const N: usize> Matrix<N, N> {
+    /// Multiplies this matrix by `other` using the Strassen-Winograd algorithm, falling back to the
+    /// blocked kernel for blocks of size `cutoff` or smaller.
+    ///
+    /// The result can differ from [`Matrix::multiply`] by rounding, as the entries are summed in a
+    /// different order.
+    pub fn multiply_strassen(&self, other: &Matrix<N, N>, cutoff: usize) -> Matrix<N, N> {
+        Matrix {
+            matrix: strassen(N, &self.matrix, &other.matrix, cutoff.max(1)),
+        }
+    }
+}
+
+/// Returns the product of the `n` x `n` row-major matrices `a` and `b`.
+fn strassen(n: usize, a: &[f64], b: &[f64], cutoff: usize) -> Vec<f64> {
+    if n <= cutoff {
+        let mut c = vec![0.0; n * n];
+        gemm(n, n, n, a, b, &mut c);
+
+        return c;
+    }
+
+    if n % 2 == 1 {
+        let c = strassen(n + 1, &pad(n, a), &pad(n, b), cutoff);
+
+        return crop(n + 1, &c);
+    }
+
+    let h = n / 2;
+    let [a11, a12, a21, a22] = quadrants(n, a);
+    let [b11, b12, b21, b22] = quadrants(n, b);
+
+    let s1 = add(&a21, &a22);
+    let s2 = sub(&s1, &a11);
+    let s3 = sub(&a11, &a21);
+    let s4 = sub(&a12, &s2);
+
+    let t1 = sub(&b12, &b11);
+    let t2 = sub(&b22, &t1);
+    let t3 = sub(&b22, &b12);
+    let t4 = sub(&t2, &b21);
+
+    let m1 = strassen(h, &a11, &b11, cutoff);
+    let m2 = strassen(h, &a12, &b21, cutoff);
+    let m3 = strassen(h, &s4, &b22, cutoff);
+    let m4 = strassen(h, &a22, &t4, cutoff);
+    let m5 = strassen(h, &s1, &t1, cutoff);
+    let m6 = strassen(h, &s2, &t2, cutoff);
+    let m7 = strassen(h, &s3, &t3, cutoff);
+
+    let u2 = add(&m1, &m6);
+    let u3 = add(&u2, &m7);
+    let u4 = add(&u2, &m5);
+
+    let c11 = add(&m1, &m2);
+    let c12 = add(&u4, &m3);
+    let c21 = sub(&u3, &m4);
+    let c22 = add(&u3, &m5);
+
+    join(h, [&c11, &c12, &c21, &c22])
+}
+
+/// Splits an `n` x `n` matrix of even size into its four quadrants, in row-major order.
+fn quadrants(n: usize, data: &[f64]) -> [Vec<f64>; 4] {
+    let h = n / 2;
+    let block = |row: usize, column: usize| -> Vec<f64> {
+        (0..h)
+            .flat_map(|i| &data[(row + i) * n + column..][..h])
+            .copied()
+            .collect()
+    };
+
+    [block(0, 0), block(0, h), block(h, 0), block(h, h)]
+}
+
+/// Joins four `h` x `h` quadrants, in row-major order, into one matrix.
+fn join(h: usize, blocks: [&[f64]; 4]) -> Vec<f64> {
+    let n = 2 * h;
+    let mut data = vec![0.0; n * n];
+
+    for (b, block) in blocks.iter().enumerate() {
+        let (row, column) = ((b / 2) * h, (b % 2) * h);
+
+        for i in 0..h {
+            data[(row + i) * n + column..][..h].copy_from_slice(&block[i * h..][..h]);
+        }
+    }
+
+    data
+}
+
+/// Pads an `n` x `n` matrix with a trailing row and column of zeros.
+fn pad(n: usize, data: &[f64]) -> Vec<f64> {
+    let mut padded = vec![0.0; (n + 1) * (n + 1)];
+
+    for (i, row) in data.chunks(n).enumerate() {
+        padded[i * (n + 1)..][..n].copy_from_slice(row);
+    }
+
+    padded
+}
+
+/// Removes the trailing row and column of an `n` x `n` matrix.
+fn crop(n: usize, data: &[f64]) -> Vec<f64> {
+    data.chunks(n)
+        .take(n - 1)
+        .flat_map(|row| &row[..n - 1])
+        .copied()
+        .collect()
+}
+
+fn add(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut out = vec![0.0; a.len()];
+    simd::add(a, b, &mut out);
+
+    out
+}
+
+fn sub(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut out = vec![0.0; a.len()];
+    simd::sub(a, b, &mut out);
+
+    out
+}
+
+#[cfg(test)]
+mod strassen_tests {
+    use super::*;
+
+    fn example<const N: usize>(seed: usize) -> Matrix<N, N> {
+        let data = (0..N * N)
+            .map(|i| ((i * 37 + seed) % 23) as f64 / 7.0 - 1.5)
+            .collect();
+
+        Matrix::new(data).unwrap()
+    }
+
+    fn assert_close<const N: usize>(a: &Matrix<N, N>, b: &Matrix<N, N>) {
+        for (x, y) in a.data().iter().zip(b.data()) {
+            assert!((x - y).abs() < 1e-9, "{} != {}", x, y);
+        }
+    }
+
+    #[test]
+    fn test_against_multiply() {
+        let a: Matrix<64, 64> = example(1);
+        let b: Matrix<64, 64> = example(8);
+
+        assert_close(&a.multiply_strassen(&b, 8), &a.multiply(&b));
+    }
+
+    #[test]
+    fn test_odd_sizes() {
+        let a: Matrix<37, 37> = example(3);
+        let b: Matrix<37, 37> = example(5);
+        assert_close(&a.multiply_strassen(&b, 4), &a.multiply(&b));
+
+        let a: Matrix<1, 1> = example(2);
+        let b: Matrix<1, 1> = example(6);
+        assert_close(&a.multiply_strassen(&b, 0), &a.multiply(&b));
+    }
+
+    #[test]
+    fn test_below_cutoff_matches_exactly() {
+        let a: Matrix<20, 20> = example(4);
+        let b: Matrix<20, 20> = example(9);
+
+        assert_eq!(a.multiply_strassen(&b, DEFAULT_CUTOFF), a.multiply(&b));
+    }
+}
@@ -0,0 +1,224 @@
+//! Singular value decomposition by one-sided Jacobi rotations, used for the pseudo-inverse.
+
+use crate::{error::MatrixError, matrix::Matrix, simd};
+
+/// The most sweeps over all column pairs before giving up. Jacobi converges quadratically, so a handful
+/// is usually enough.
+const MAX_SWEEPS: usize = 60;
+
+/// The thin factorization `A = UΣVᵀ` of an `m` x `n` matrix, with `k = min(m, n)` singular values.
+pub(crate) struct Svd {
+    /// The `k` left singular vectors, each of length `m`.
+    u: Vec<Vec<f64>>,
+    /// The singular values, largest first.
+    sigma: Vec<f64>,
+    /// The `k` right singular vectors, each of length `n`.
+    v: Vec<Vec<f64>>,
+}
+
+impl Svd {
+    /// Factors the `m` x `n` row-major matrix `data`.
+    ///
+    /// Returns a `NoConvergence` error if the rotations do not orthogonalize the columns.
+    pub(crate) fn new(m: usize, n: usize, data: &[f64]) -> Result<Self, MatrixError> {
+        // Work on whichever of `A` and `Aᵀ` has at least as many rows as columns.
+        let transposed = m < n;
+        let (rows, columns) = if transposed { (n, m) } else { (m, n) };
+        let entry = |i: usize, j: usize| match transposed {
+            true => data[j * n + i],
+            false => data[i * n + j],
+        };
+
+        let mut a: Vec<Vec<f64>> = (0..columns)
+            .map(|j| (0..rows).map(|i| entry(i, j)).collect())
+            .collect();
+        let mut v: Vec<Vec<f64>> = (0..columns)
+            .map(|j| {
+                (0..columns)
+                    .map(|i| if i == j { 1.0 } else { 0.0 })
+                    .collect()
+            })
+            .collect();
+
+        let mut converged = false;
+        for _ in 0..MAX_SWEEPS {
+            let mut rotated = false;
+
+            for p in 0..columns {
+                for q in p + 1..columns {
+                    let alpha = simd::dot(&a[p], &a[p]);
+                    let beta = simd::dot(&a[q], &a[q]);
+                    let gamma = simd::dot(&a[p], &a[q]);
+
+                    if gamma == 0.0 || gamma.abs() <= f64::EPSILON * (alpha * beta).sqrt() {
+                        continue;
+                    }
+                    rotated = true;
+
+                    // The rotation that makes columns `p` and `q` orthogonal.
+                    let zeta = (beta - alpha) / (2.0 * gamma);
+                    let t = zeta.signum() / (zeta.abs() + (1.0 + zeta * zeta).sqrt());
+                    let c = 1.0 / (1.0 + t * t).sqrt();
+                    let s = c * t;
+
+                    rotate(&mut a, p, q, c, s);
+                    rotate(&mut v, p, q, c, s);
+                }
+            }
+
+            if !rotated {
+                converged = true;
+                break;
+            }
+        }
+
+        if !converged {
+            return Err(MatrixError::NoConvergence);
+        }
+
+        // The columns are now `σᵢuᵢ`, so their norms are the singular values.
+        let mut order: Vec<(f64, usize)> =
+            a.iter().map(|column| simd::norm(column)).zip(0..).collect();
+        order.sort_by(|x, y| y.0.total_cmp(&x.0));
+
+        let sigma: Vec<f64> = order.iter().map(|&(sigma, _)| sigma).collect();
+        let left: Vec<Vec<f64>> = order
+            .iter()
+            .map(|&(sigma, j)| match sigma {
+                0.0 => vec![0.0; rows],
+                _ => a[j].iter().map(|x| x / sigma).collect(),
+            })
+            .collect();
+        let right: Vec<Vec<f64>> = order.iter().map(|&(_, j)| v[j].clone()).collect();
+
+        let (u, v) = if transposed {
+            (right, left)
+        } else {
+            (left, right)
+        };
+
+        Ok(Self { u, sigma, v })
+    }
+
+    /// Returns `VΣ⁺Uᵀ` in row-major order, treating singular values at or below `cutoff` as zero.
+    pub(crate) fn pseudo_inverse(&self, cutoff: f64) -> Vec<f64> {
+        let m = self.u.first().map_or(0, Vec::len);
+        let n = self.v.first().map_or(0, Vec::len);
+        let mut inverse = vec![0.0; n * m];
+
+        for ((u, v), &sigma) in self.u.iter().zip(&self.v).zip(&self.sigma) {
+            if sigma <= cutoff {
+                continue;
+            }
+
+            for i in 0..n {
+                let scale = v[i] / sigma;
+                for j in 0..m {
+                    inverse[i * m + j] += scale * u[j];
+                }
+            }
+        }
+
+        inverse
+    }
+}
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    /// Returns the `min(R, C)` singular values of the matrix, largest first.
+    ///
+    /// Returns a `NoConvergence` error if the Jacobi iteration does not settle.
+    pub fn singular_values(&self) -> Result<Vec<f64>, MatrixError> {
+        Ok(Svd::new(R, C, &self.matrix)?.sigma)
+    }
+
+    /// Returns the Moore–Penrose pseudo-inverse `A⁺`, computed through a singular value decomposition.
+    ///
+    /// Singular values at or below `tol` times the largest one are treated as zero, which keeps
+    /// near-dependent rows or columns from blowing up the result. `max(R, C) * f64::EPSILON` is a
+    /// common choice. Returns a `NoConvergence` error if the decomposition does not settle.
+    pub fn pseudo_inverse(&self, tol: f64) -> Result<Matrix<C, R>, MatrixError> {
+        let svd = Svd::new(R, C, &self.matrix)?;
+        let largest = svd.sigma.first().copied().unwrap_or(0.0);
+
+        Ok(Matrix {
+            matrix: svd.pseudo_inverse(tol * largest),
+        })
+    }
+}
+
+/// Replaces columns `p` and `q` by `c·p - s·q` and `s·p + c·q`.
+fn rotate(columns: &mut [Vec<f64>], p: usize, q: usize, c: f64, s: f64) {
+    let (left, right) = columns.split_at_mut(q);
+    let (x, y) = (&mut left[p], &mut right[0]);
+
+    for (x, y) in x.iter_mut().zip(y.iter_mut()) {
+        let (a, b) = (*x, *y);
+        *x = c * a - s * b;
+        *y = s * a + c * b;
+    }
+}
+
+#[cfg(test)]
+mod svd_tests {
+    use super::*;
+
+    fn assert_close<const R: usize, const C: usize>(a: &Matrix<R, C>, b: &Matrix<R, C>) {
+        for (x, y) in a.data().iter().zip(b.data()) {
+            assert!((x - y).abs() < 1e-10, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_singular_values() {
+        let matrix: Matrix<3, 2> = Matrix::new(vec![0.0, 3.0, -2.0, 0.0, 0.0, 0.0]).unwrap();
+        assert_eq!(matrix.singular_values().unwrap(), vec![3.0, 2.0]);
+
+        let matrix: Matrix<2, 3> = Matrix::new(vec![3.0, 0.0, 4.0, 0.0, 1.0, 0.0]).unwrap();
+        let sigma = matrix.singular_values().unwrap();
+        assert!((sigma[0] - 5.0).abs() < 1e-12 && (sigma[1] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_pseudo_inverse_of_invertible_matrix() {
+        let matrix: Matrix<3, 3> =
+            Matrix::new(vec![0.0, 2.0, 1.0, 1.0, 1.0, 0.0, 3.0, 0.0, 1.0]).unwrap();
+
+        assert_close(
+            &matrix.pseudo_inverse(1e-12).unwrap(),
+            &matrix.inverse().unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_pseudo_inverse_of_wide_matrix() {
+        // A redundant arm: two task-space rows, three joints.
+        let jacobian: Matrix<2, 3> = Matrix::new(vec![1.0, 2.0, 0.5, -1.0, 0.0, 3.0]).unwrap();
+        let pinv = jacobian.pseudo_inverse(1e-12).unwrap();
+
+        assert_close(&jacobian.multiply(&pinv), &Matrix::new_identity_matrix(2));
+    }
+
+    #[test]
+    fn test_pseudo_inverse_of_rank_deficient_matrix() {
+        // The second column is twice the first.
+        let matrix: Matrix<3, 2> = Matrix::new(vec![1.0, 2.0, 2.0, 4.0, 3.0, 6.0]).unwrap();
+        let pinv = matrix.pseudo_inverse(1e-12).unwrap();
+
+        // The Penrose conditions `AA⁺A = A` and `A⁺AA⁺ = A⁺`.
+        assert_close(&matrix.multiply(&pinv).multiply(&matrix), &matrix);
+        assert_close(&pinv.multiply(&matrix).multiply(&pinv), &pinv);
+
+        // For a rank one matrix `abᵀ`, the pseudo-inverse is `baᵀ / (|a|²|b|²)`.
+        let expected: Matrix<2, 3> = Matrix::new(
+            [1.0, 2.0, 3.0, 2.0, 4.0, 6.0]
+                .iter()
+                .map(|x| x / 70.0)
+                .collect(),
+        )
+        .unwrap();
+        assert_close(&pinv, &expected);
+
+        let zero: Matrix<2, 2> = Matrix::new(vec![0.0; 4]).unwrap();
+        assert_eq!(zero.pseudo_inverse(1e-12).unwrap(), zero);
+    }
+}
@@ -1,6 +1,6 @@
 //! Matrix-transformation related functionality.
 
-use crate::{matrix::Matrix, vector::VectorN};
+use crate::{matrix::Matrix, parallel, vector::VectorN};
 
 use super::mtuple::Direction;
 
@@ -19,6 +19,11 @@ impl<const N: usize> Matrix<N, N> {
 
         newvec
     }
+
+    /// Transforms every vector in `vectors` through this matrix
+    pub fn transform_batch(&self, vectors: &[VectorN<N>]) -> Vec<VectorN<N>> {
+        parallel::map(vectors, N * N, |vector| self.transform(vector))
+    }
 }
 
 #[cfg(test)]
@@ -62,4 +67,23 @@ mod test_matrix_transformations {
         assert_eq!(transformed_vec.data(), &[35.0, -21.0, 18.0]);
     }
 
+    #[test]
+    fn test_batch_transformation() {
+        #[rustfmt::skip]
+        let data = vec![
+            0.0, -1.0,
+            1.0, 0.0
+        ];
+
+        let matrix: Matrix<2, 2> = Matrix::new(data).unwrap();
+
+        let vectors: Vec<VectorN<2>> = (0..5000).map(|i| VectorN::new([i as f64, 1.0])).collect();
+
+        let transformed = matrix.transform_batch(&vectors);
+
+        assert_eq!(transformed.len(), 5000);
+        assert_eq!(transformed[0].data(), &[-1.0, 0.0]);
+        assert_eq!(transformed[4999].data(), &[-1.0, 4999.0]);
+    }
+
 }
@@ -0,0 +1,433 @@
+//! Upper and lower triangular matrices, which can be solved against by substitution.
+//!
+//! Both can have a unit diagonal, in which case the stored diagonal is ignored and treated as ones, as
+//! it is for the `L` factor of an LU decomposition.
+
+use crate::{error::MatrixError, matrix::Matrix, vector::VectorN};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Side {
+    Upper,
+    Lower,
+}
+
+impl Side {
+    /// Checks whether entry `(i, j)` can be nonzero.
+    fn contains(self, i: usize, j: usize) -> bool {
+        match self {
+            Side::Upper => i <= j,
+            Side::Lower => i >= j,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// An `N` x `N` matrix whose entries below the diagonal are zero.
+pub struct UpperTriangular<const N: usize> {
+    matrix: Matrix<N, N>,
+    unit: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// An `N` x `N` matrix whose entries above the diagonal are zero.
+pub struct LowerTriangular<const N: usize> {
+    matrix: Matrix<N, N>,
+    unit: bool,
+}
+
+impl<const N: usize> UpperTriangular<N> {
+    /// Wraps an upper triangular matrix, returning a `NotTriangular` error if an entry below the
+    /// diagonal is nonzero.
+    pub fn new(matrix: Matrix<N, N>) -> Result<Self, MatrixError> {
+        check(Side::Upper, &matrix)?;
+
+        Ok(Self {
+            matrix,
+            unit: false,
+        })
+    }
+
+    /// Wraps an upper triangular matrix with ones on the diagonal, ignoring the stored diagonal.
+    pub fn new_unit(matrix: Matrix<N, N>) -> Result<Self, MatrixError> {
+        check(Side::Upper, &matrix)?;
+
+        Ok(Self { matrix, unit: true })
+    }
+
+    /// Takes the upper triangle of `matrix`, including the diagonal, and drops the rest.
+    pub fn from_upper(matrix: &Matrix<N, N>) -> Self {
+        Self {
+            matrix: triangle(Side::Upper, matrix),
+            unit: false,
+        }
+    }
+
+    /// Checks whether the diagonal is all ones by construction.
+    pub fn is_unit(&self) -> bool {
+        self.unit
+    }
+
+    /// Returns the entry at row `i` and column `j`.
+    pub fn get(&self, i: usize, j: usize) -> Option<f64> {
+        entry(Side::Upper, &self.matrix, self.unit, i, j)
+    }
+
+    /// Returns the matrix as a plain dense matrix.
+    pub fn to_dense(&self) -> Matrix<N, N> {
+        dense(&self.matrix, self.unit)
+    }
+
+    /// Returns the transpose, which is lower triangular.
+    pub fn transpose(&self) -> LowerTriangular<N> {
+        LowerTriangular {
+            matrix: self.matrix.transpose(),
+            unit: self.unit,
+        }
+    }
+
+    /// Returns the determinant, the product of the diagonal.
+    pub fn determinant(&self) -> f64 {
+        determinant(&self.matrix, self.unit)
+    }
+
+    /// Solves `Ux = b` by back substitution, returning a `Singular` error if the diagonal has a zero.
+    pub fn solve_vector(&self, b: &VectorN<N>) -> Result<VectorN<N>, MatrixError> {
+        let mut x = b.data().to_vec();
+        substitute(Side::Upper, &self.matrix, self.unit, &mut x, 1)?;
+
+        Ok(VectorN::new(x.try_into().unwrap()))
+    }
+
+    /// Solves `UX = B` by back substitution, returning a `Singular` error if the diagonal has a zero.
+    pub fn solve<const K: usize>(&self, b: &Matrix<N, K>) -> Result<Matrix<N, K>, MatrixError> {
+        let mut x = b.matrix.clone();
+        substitute(Side::Upper, &self.matrix, self.unit, &mut x, K)?;
+
+        Ok(Matrix { matrix: x })
+    }
+
+    /// Returns the product with another upper triangular matrix, which is upper triangular again.
+    pub fn multiply(&self, other: &UpperTriangular<N>) -> UpperTriangular<N> {
+        Self {
+            matrix: self.to_dense().multiply(&other.to_dense()),
+            unit: self.unit && other.unit,
+        }
+    }
+
+    /// Returns the product with a general matrix.
+    pub fn multiply_matrix<const K: usize>(&self, other: &Matrix<N, K>) -> Matrix<N, K> {
+        self.to_dense().multiply(other)
+    }
+
+    /// Returns the inverse, which is upper triangular again, or a `Singular` error if the diagonal has a
+    /// zero.
+    pub fn inverse(&self) -> Result<UpperTriangular<N>, MatrixError> {
+        Ok(Self {
+            matrix: inverse(Side::Upper, &self.matrix, self.unit)?,
+            unit: self.unit,
+        })
+    }
+}
+
+impl<const N: usize> LowerTriangular<N> {
+    /// Wraps a lower triangular matrix, returning a `NotTriangular` error if an entry above the
+    /// diagonal is nonzero.
+    pub fn new(matrix: Matrix<N, N>) -> Result<Self, MatrixError> {
+        check(Side::Lower, &matrix)?;
+
+        Ok(Self {
+            matrix,
+            unit: false,
+        })
+    }
+
+    /// Wraps a lower triangular matrix with ones on the diagonal, ignoring the stored diagonal.
+    pub fn new_unit(matrix: Matrix<N, N>) -> Result<Self, MatrixError> {
+        check(Side::Lower, &matrix)?;
+
+        Ok(Self { matrix, unit: true })
+    }
+
+    /// Takes the lower triangle of `matrix`, including the diagonal, and drops the rest.
+    pub fn from_lower(matrix: &Matrix<N, N>) -> Self {
+        Self {
+            matrix: triangle(Side::Lower, matrix),
+            unit: false,
+        }
+    }
+
+    /// Checks whether the diagonal is all ones by construction.
+    pub fn is_unit(&self) -> bool {
+        self.unit
+    }
+
+    /// Returns the entry at row `i` and column `j`.
+    pub fn get(&self, i: usize, j: usize) -> Option<f64> {
+        entry(Side::Lower, &self.matrix, self.unit, i, j)
+    }
+
+    /// Returns the matrix as a plain dense matrix.
+    pub fn to_dense(&self) -> Matrix<N, N> {
+        dense(&self.matrix, self.unit)
+    }
+
+    /// Returns the transpose, which is upper triangular.
+    pub fn transpose(&self) -> UpperTriangular<N> {
+        UpperTriangular {
+            matrix: self.matrix.transpose(),
+            unit: self.unit,
+        }
+    }
+
+    /// Returns the determinant, the product of the diagonal.
+    pub fn determinant(&self) -> f64 {
+        determinant(&self.matrix, self.unit)
+    }
+
+    /// Solves `Lx = b` by forward substitution, returning a `Singular` error if the diagonal has a zero.
+    pub fn solve_vector(&self, b: &VectorN<N>) -> Result<VectorN<N>, MatrixError> {
+        let mut x = b.data().to_vec();
+        substitute(Side::Lower, &self.matrix, self.unit, &mut x, 1)?;
+
+        Ok(VectorN::new(x.try_into().unwrap()))
+    }
+
+    /// Solves `LX = B` by forward substitution, returning a `Singular` error if the diagonal has a zero.
+    pub fn solve<const K: usize>(&self, b: &Matrix<N, K>) -> Result<Matrix<N, K>, MatrixError> {
+        let mut x = b.matrix.clone();
+        substitute(Side::Lower, &self.matrix, self.unit, &mut x, K)?;
+
+        Ok(Matrix { matrix: x })
+    }
+
+    /// Returns the product with another lower triangular matrix, which is lower triangular again.
+    pub fn multiply(&self, other: &LowerTriangular<N>) -> LowerTriangular<N> {
+        Self {
+            matrix: self.to_dense().multiply(&other.to_dense()),
+            unit: self.unit && other.unit,
+        }
+    }
+
+    /// Returns the product with a general matrix.
+    pub fn multiply_matrix<const K: usize>(&self, other: &Matrix<N, K>) -> Matrix<N, K> {
+        self.to_dense().multiply(other)
+    }
+
+    /// Returns the inverse, which is lower triangular again, or a `Singular` error if the diagonal has a
+    /// zero.
+    pub fn inverse(&self) -> Result<LowerTriangular<N>, MatrixError> {
+        Ok(Self {
+            matrix: inverse(Side::Lower, &self.matrix, self.unit)?,
+            unit: self.unit,
+        })
+    }
+}
+
+fn check<const N: usize>(side: Side, matrix: &Matrix<N, N>) -> Result<(), MatrixError> {
+    let outside = (0..N * N).any(|index| {
+        let (i, j) = (index / N, index % N);
+
+        !side.contains(i, j) && matrix.matrix[index] != 0.0
+    });
+
+    if outside {
+        return Err(MatrixError::NotTriangular);
+    }
+
+    Ok(())
+}
+
+fn triangle<const N: usize>(side: Side, matrix: &Matrix<N, N>) -> Matrix<N, N> {
+    let matrix = (0..N * N)
+        .map(|index| match side.contains(index / N, index % N) {
+            true => matrix.matrix[index],
+            false => 0.0,
+        })
+        .collect();
+
+    Matrix { matrix }
+}
+
+fn entry<const N: usize>(
+    side: Side,
+    matrix: &Matrix<N, N>,
+    unit: bool,
+    i: usize,
+    j: usize,
+) -> Option<f64> {
+    if i >= N || j >= N {
+        return None;
+    }
+
+    Some(match (i == j && unit, side.contains(i, j)) {
+        (true, _) => 1.0,
+        (false, true) => matrix.matrix[i * N + j],
+        (false, false) => 0.0,
+    })
+}
+
+fn dense<const N: usize>(matrix: &Matrix<N, N>, unit: bool) -> Matrix<N, N> {
+    let mut dense = matrix.clone();
+
+    if unit {
+        (0..N).for_each(|i| dense.matrix[i * N + i] = 1.0);
+    }
+
+    dense
+}
+
+fn determinant<const N: usize>(matrix: &Matrix<N, N>, unit: bool) -> f64 {
+    match unit {
+        true => 1.0,
+        false => (0..N).map(|i| matrix.matrix[i * N + i]).product(),
+    }
+}
+
+/// Overwrites the `N` x `m` row-major matrix `b` with the solution of `TX = b` by substitution.
+fn substitute<const N: usize>(
+    side: Side,
+    matrix: &Matrix<N, N>,
+    unit: bool,
+    b: &mut [f64],
+    m: usize,
+) -> Result<(), MatrixError> {
+    let t = &matrix.matrix;
+
+    if !unit && (0..N).any(|i| t[i * N + i] == 0.0) {
+        return Err(MatrixError::Singular);
+    }
+
+    // Forward substitution goes from the top, back substitution from the bottom.
+    let order: Vec<usize> = match side {
+        Side::Lower => (0..N).collect(),
+        Side::Upper => (0..N).rev().collect(),
+    };
+
+    for (done, &i) in order.iter().enumerate() {
+        for &k in &order[..done] {
+            let factor = t[i * N + k];
+
+            for j in 0..m {
+                b[i * m + j] -= factor * b[k * m + j];
+            }
+        }
+
+        if !unit {
+            let diagonal = t[i * N + i];
+            b[i * m..(i + 1) * m]
+                .iter_mut()
+                .for_each(|f| *f /= diagonal);
+        }
+    }
+
+    Ok(())
+}
+
+fn inverse<const N: usize>(
+    side: Side,
+    matrix: &Matrix<N, N>,
+    unit: bool,
+) -> Result<Matrix<N, N>, MatrixError> {
+    let mut inverse: Matrix<N, N> = Matrix::new_identity_matrix(N);
+    substitute(side, matrix, unit, &mut inverse.matrix, N)?;
+
+    Ok(inverse)
+}
+
+#[cfg(test)]
+mod triangular_tests {
+    use super::*;
+
+    fn upper() -> UpperTriangular<3> {
+        let matrix = Matrix::new(vec![2.0, 1.0, -1.0, 0.0, 4.0, 2.0, 0.0, 0.0, 0.5]).unwrap();
+
+        UpperTriangular::new(matrix).unwrap()
+    }
+
+    fn lower() -> LowerTriangular<3> {
+        let matrix = Matrix::new(vec![1.0, 0.0, 0.0, 2.0, 1.0, 0.0, -3.0, 4.0, 1.0]).unwrap();
+
+        LowerTriangular::new_unit(matrix).unwrap()
+    }
+
+    #[test]
+    fn test_construction() {
+        let full: Matrix<2, 2> = Matrix::new(vec![1.0, 2.0, 3.0, 4.0]).unwrap();
+
+        assert!(matches!(
+            UpperTriangular::new(full.clone()),
+            Err(MatrixError::NotTriangular)
+        ));
+        assert!(LowerTriangular::new(full.clone()).is_err());
+
+        assert_eq!(
+            UpperTriangular::from_upper(&full).to_dense().data(),
+            &[1.0, 2.0, 0.0, 4.0]
+        );
+        assert_eq!(
+            LowerTriangular::from_lower(&full).to_dense().data(),
+            &[1.0, 0.0, 3.0, 4.0]
+        );
+
+        // The stored diagonal of a unit triangular matrix is ignored.
+        let stored: Matrix<2, 2> = Matrix::new(vec![5.0, 0.0, 3.0, 7.0]).unwrap();
+        let unit = LowerTriangular::new_unit(stored).unwrap();
+        assert_eq!(unit.get(0, 0), Some(1.0));
+        assert_eq!(unit.get(0, 1), Some(0.0));
+        assert_eq!(unit.get(1, 0), Some(3.0));
+        assert_eq!(unit.get(2, 0), None);
+        assert_eq!(unit.determinant(), 1.0);
+    }
+
+    #[test]
+    fn test_substitution() {
+        let u = upper();
+        let b = VectorN::new([3.0, 10.0, 1.0]);
+        let x = u.solve_vector(&b).unwrap();
+        assert_eq!(x.data(), &[1.75, 1.5, 2.0]);
+
+        let l = lower();
+        let b = VectorN::new([1.0, 4.0, 7.0]);
+        let x = l.solve_vector(&b).unwrap();
+        assert_eq!(x.data(), &[1.0, 2.0, 2.0]);
+
+        let b: Matrix<3, 2> = Matrix::new(vec![3.0, 2.0, 10.0, 4.0, 1.0, 0.5]).unwrap();
+        let x = u.solve(&b).unwrap();
+        assert_eq!(u.multiply_matrix(&x), b);
+
+        let singular =
+            UpperTriangular::from_upper(&Matrix::<2, 2>::new(vec![1.0, 2.0, 0.0, 0.0]).unwrap());
+        assert!(matches!(
+            singular.solve_vector(&VectorN::new([1.0, 1.0])),
+            Err(MatrixError::Singular)
+        ));
+    }
+
+    #[test]
+    fn test_inverse_and_products() {
+        let u = upper();
+        let inverse = u.inverse().unwrap();
+        assert!(check(Side::Upper, &inverse.to_dense()).is_ok());
+        assert_eq!(
+            u.multiply(&inverse).to_dense(),
+            Matrix::new_identity_matrix(3)
+        );
+
+        let l = lower();
+        let inverse = l.inverse().unwrap();
+        assert!(inverse.is_unit());
+        assert_eq!(
+            l.multiply(&inverse).to_dense(),
+            Matrix::new_identity_matrix(3)
+        );
+
+        assert_eq!(u.transpose().to_dense(), u.to_dense().transpose());
+    }
+
+    #[test]
+    fn test_determinant() {
+        assert_eq!(upper().determinant(), 4.0);
+        assert_eq!(upper().determinant(), upper().to_dense().determinant());
+        assert_eq!(lower().determinant(), 1.0);
+    }
+}
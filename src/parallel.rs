@@ -0,0 +1,207 @@
+//! Multithreaded execution of large operations, enabled with the `parallel` feature.
+//!
+//! Matrix multiplication, element-wise operations, dot products, row and column reductions, the LU
+//! elimination and batched transformations are split across rayon's global thread pool once they involve at least `threshold()` scalar operations.
+//! Smaller operations always run on the calling thread, where the overhead of splitting them up would
+//! outweigh the gains. Operations started inside `rayon::ThreadPool::install` use that pool instead.
+//!
+//! Without the `parallel` feature everything runs serially and this module has no public items.
+
+#[cfg(feature = "parallel")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[cfg(feature = "parallel")]
+use crate::error::MatrixError;
+
+/// The default number of scalar operations above which work is split across threads.
+#[cfg(feature = "parallel")]
+pub const DEFAULT_THRESHOLD: usize = 1 << 15;
+
+/// The number of entries each task handles in element-wise operations and reductions.
+const CHUNK: usize = 4096;
+
+#[cfg(feature = "parallel")]
+static THRESHOLD: AtomicUsize = AtomicUsize::new(DEFAULT_THRESHOLD);
+
+/// Returns the number of scalar operations above which work is split across threads.
+#[cfg(feature = "parallel")]
+pub fn threshold() -> usize {
+    THRESHOLD.load(Ordering::Relaxed)
+}
+
+/// Sets the number of scalar operations above which work is split across threads.
+#[cfg(feature = "parallel")]
+pub fn set_threshold(threshold: usize) {
+    THRESHOLD.store(threshold, Ordering::Relaxed);
+}
+
+/// Configures the global thread pool to use `num_threads` threads, or one per core if it is `0`.
+///
+/// This has to be called before any parallel operation runs, as the global pool can only be set up once.
+#[cfg(feature = "parallel")]
+pub fn configure_thread_pool(num_threads: usize) -> Result<(), MatrixError> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .build_global()
+        .map_err(|error| MatrixError::ThreadPool(error.to_string()))
+}
+
+/// Checks whether an operation doing `work` scalar operations should be split across threads.
+#[cfg(feature = "parallel")]
+pub(crate) fn should_parallelize(work: usize) -> bool {
+    work >= threshold() && rayon::current_num_threads() > 1
+}
+
+/// Runs an element-wise kernel over `a`, `b` and `out`, splitting them into chunks if they are large enough.
+pub(crate) fn binary(
+    a: &[f64],
+    b: &[f64],
+    out: &mut [f64],
+    kernel: fn(&[f64], &[f64], &mut [f64]),
+) {
+    #[cfg(feature = "parallel")]
+    if should_parallelize(out.len()) {
+        out.par_chunks_mut(CHUNK)
+            .zip(a.par_chunks(CHUNK))
+            .zip(b.par_chunks(CHUNK))
+            .for_each(|((out, a), b)| kernel(a, b, out));
+
+        return;
+    }
+
+    kernel(a, b, out)
+}
+
+/// Runs an in-place kernel over `data`, splitting it into chunks if it is large enough.
+pub(crate) fn unary(data: &mut [f64], kernel: impl Fn(&mut [f64]) + Sync + Send) {
+    #[cfg(feature = "parallel")]
+    if should_parallelize(data.len()) {
+        data.par_chunks_mut(CHUNK).for_each(kernel);
+
+        return;
+    }
+
+    kernel(data)
+}
+
+/// Sums a reduction kernel over `a` and `b`, splitting them into chunks if they are large enough.
+///
+/// The kernel always runs on the same fixed chunks, whose results are added in order, so the sum does not
+/// depend on whether or how the work is split across threads.
+pub(crate) fn reduce(a: &[f64], b: &[f64], kernel: fn(&[f64], &[f64]) -> f64) -> f64 {
+    #[cfg(feature = "parallel")]
+    if should_parallelize(a.len()) {
+        let partials: Vec<f64> = a
+            .par_chunks(CHUNK)
+            .zip(b.par_chunks(CHUNK))
+            .map(|(a, b)| kernel(a, b))
+            .collect();
+
+        return partials.iter().sum();
+    }
+
+    a.chunks(CHUNK)
+        .zip(b.chunks(CHUNK))
+        .map(|(a, b)| kernel(a, b))
+        .sum()
+}
+
+/// Calls `f` on every row of the row-major `data`, whose rows have `columns` entries, splitting the rows
+/// across threads if the `work` they take in total is large enough.
+#[cfg_attr(not(feature = "parallel"), allow(unused_variables))]
+pub(crate) fn rows(
+    data: &mut [f64],
+    columns: usize,
+    work: usize,
+    f: impl Fn(&mut [f64]) + Sync + Send,
+) {
+    #[cfg(feature = "parallel")]
+    if should_parallelize(work) {
+        data.par_chunks_mut(columns.max(1)).for_each(f);
+
+        return;
+    }
+
+    data.chunks_mut(columns.max(1)).for_each(f)
+}
+
+/// Maps every item, where each item takes about `work` scalar operations, splitting them across threads
+/// if there is enough work in total.
+#[cfg_attr(not(feature = "parallel"), allow(unused_variables))]
+pub(crate) fn map<T: Sync, U: Send>(
+    items: &[T],
+    work: usize,
+    f: impl Fn(&T) -> U + Sync + Send,
+) -> Vec<U> {
+    #[cfg(feature = "parallel")]
+    if should_parallelize(items.len() * work) {
+        return items.par_iter().map(f).collect();
+    }
+
+    items.iter().map(f).collect()
+}
+
+#[cfg(test)]
+mod parallel_tests {
+    use super::*;
+    use crate::simd;
+
+    #[test]
+    fn test_large_elementwise_operations() {
+        let a: Vec<f64> = (0..100_000).map(|i| i as f64).collect();
+        let b: Vec<f64> = (0..100_000).map(|i| (i % 7) as f64).collect();
+        let mut out = vec![0.0; 100_000];
+
+        binary(&a, &b, &mut out, simd::add);
+        assert!(out.iter().enumerate().all(|(i, &f)| f == a[i] + b[i]));
+
+        unary(&mut out, |chunk| simd::scale(chunk, 2.0));
+        assert!(out
+            .iter()
+            .enumerate()
+            .all(|(i, &f)| f == 2.0 * (a[i] + b[i])));
+
+        let expected: f64 = a.iter().zip(&b).map(|(x, y)| x * y).sum();
+        assert_eq!(reduce(&a, &b, simd::dot), expected);
+    }
+
+    #[test]
+    fn test_reduce_is_deterministic() {
+        // Values of very different sizes, so any change in the order of additions changes the rounding.
+        let a: Vec<f64> = (0..100_000)
+            .map(|i| (i as f64 * 0.37).sin() * 1e10_f64.powi(i % 3))
+            .collect();
+        let b: Vec<f64> = (0..100_000).map(|i| (i as f64 * 0.11).cos()).collect();
+
+        let expected: f64 = a
+            .chunks(CHUNK)
+            .zip(b.chunks(CHUNK))
+            .map(|(a, b)| simd::dot(a, b))
+            .sum();
+        assert_eq!(reduce(&a, &b, simd::dot).to_bits(), expected.to_bits());
+
+        #[cfg(feature = "parallel")]
+        for threads in [2, 3, 5] {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let sum = pool.install(|| reduce(&a, &b, simd::dot));
+
+            assert_eq!(sum.to_bits(), expected.to_bits());
+        }
+    }
+
+    #[test]
+    fn test_map_keeps_order() {
+        let items: Vec<usize> = (0..10_000).collect();
+
+        assert_eq!(
+            map(&items, 100, |i| i * 2),
+            (0..10_000).map(|i| i * 2).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,311 @@
+//! Explicitly vectorized kernels for the hot loops.
+//!
+//! On x86-64 the AVX (and FMA) versions are picked at runtime when the CPU supports them, everywhere
+//! else the portable scalar loops are used.
+
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+/// Below this length the scalar loops are used, which also keeps the results of small dot products
+/// identical to a plain left-to-right sum.
+const MIN_LEN: usize = 16;
+
+/// Checks whether the AVX and FMA kernels can be used.
+#[cfg(target_arch = "x86_64")]
+pub(crate) fn has_avx_fma() -> bool {
+    is_x86_feature_detected!("avx") && is_x86_feature_detected!("fma")
+}
+
+/// Checks whether the AVX and FMA kernels can be used.
+#[cfg(not(target_arch = "x86_64"))]
+pub(crate) fn has_avx_fma() -> bool {
+    false
+}
+
+/// Returns the dot product of two slices of the same length.
+///
+/// Panics if the lengths differ, as the vectorized kernel reads `a.len()` entries from both.
+pub(crate) fn dot(a: &[f64], b: &[f64]) -> f64 {
+    assert_eq!(a.len(), b.len(), "dot product of slices with different lengths");
+
+    #[cfg(target_arch = "x86_64")]
+    if a.len() >= MIN_LEN && has_avx_fma() {
+        // SAFETY: the required CPU features were just detected.
+        return unsafe { dot_avx(a, b) };
+    }
+
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Returns the Euclidean length of a slice.
+pub(crate) fn norm(a: &[f64]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+/// Computes `y += alpha * x`, panicking if the lengths differ.
+pub(crate) fn axpy(alpha: f64, x: &[f64], y: &mut [f64]) {
+    assert_eq!(x.len(), y.len(), "axpy on slices with different lengths");
+
+    #[cfg(target_arch = "x86_64")]
+    if x.len() >= MIN_LEN && has_avx_fma() {
+        // SAFETY: the required CPU features were just detected.
+        return unsafe { axpy_avx(alpha, x, y) };
+    }
+
+    y.iter_mut().zip(x).for_each(|(y, x)| *y += alpha * x);
+}
+
+/// Writes `a + b` into `out`, panicking if the three lengths differ.
+pub(crate) fn add(a: &[f64], b: &[f64], out: &mut [f64]) {
+    check_lengths(a, b, out);
+
+    #[cfg(target_arch = "x86_64")]
+    if a.len() >= MIN_LEN && has_avx_fma() {
+        // SAFETY: the required CPU features were just detected.
+        return unsafe { add_avx(a, b, out) };
+    }
+
+    out.iter_mut()
+        .zip(a.iter().zip(b))
+        .for_each(|(out, (a, b))| *out = a + b);
+}
+
+/// Writes `a - b` into `out`, panicking if the three lengths differ.
+pub(crate) fn sub(a: &[f64], b: &[f64], out: &mut [f64]) {
+    check_lengths(a, b, out);
+
+    #[cfg(target_arch = "x86_64")]
+    if a.len() >= MIN_LEN && has_avx_fma() {
+        // SAFETY: the required CPU features were just detected.
+        return unsafe { sub_avx(a, b, out) };
+    }
+
+    out.iter_mut()
+        .zip(a.iter().zip(b))
+        .for_each(|(out, (a, b))| *out = a - b);
+}
+
+/// Multiplies every entry by `k`.
+pub(crate) fn scale(data: &mut [f64], k: f64) {
+    #[cfg(target_arch = "x86_64")]
+    if data.len() >= MIN_LEN && has_avx_fma() {
+        // SAFETY: the required CPU features were just detected.
+        return unsafe { scale_avx(data, k) };
+    }
+
+    data.iter_mut().for_each(|f| *f *= k);
+}
+
+/// Sets every entry to `k`.
+pub(crate) fn fill(data: &mut [f64], k: f64) {
+    #[cfg(target_arch = "x86_64")]
+    if data.len() >= MIN_LEN && has_avx_fma() {
+        // SAFETY: the required CPU features were just detected.
+        return unsafe { fill_avx(data, k) };
+    }
+
+    data.fill(k);
+}
+
+/// The vectorized kernels read and write `a.len()` entries of every slice, so a mismatch would be out of
+/// bounds.
+fn check_lengths(a: &[f64], b: &[f64], out: &[f64]) {
+    assert!(
+        a.len() == b.len() && a.len() == out.len(),
+        "element-wise operation on slices with lengths {}, {} and {}",
+        a.len(),
+        b.len(),
+        out.len()
+    );
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx,fma")]
+unsafe fn dot_avx(a: &[f64], b: &[f64]) -> f64 {
+    let end = a.len() - a.len() % 8;
+
+    // Two accumulators to hide the latency of the fused multiply-adds.
+    let mut acc0 = _mm256_setzero_pd();
+    let mut acc1 = _mm256_setzero_pd();
+
+    for i in (0..end).step_by(8) {
+        let a0 = _mm256_loadu_pd(a.as_ptr().add(i));
+        let b0 = _mm256_loadu_pd(b.as_ptr().add(i));
+        let a1 = _mm256_loadu_pd(a.as_ptr().add(i + 4));
+        let b1 = _mm256_loadu_pd(b.as_ptr().add(i + 4));
+
+        acc0 = _mm256_fmadd_pd(a0, b0, acc0);
+        acc1 = _mm256_fmadd_pd(a1, b1, acc1);
+    }
+
+    let mut lanes = [0.0; 4];
+    _mm256_storeu_pd(lanes.as_mut_ptr(), _mm256_add_pd(acc0, acc1));
+
+    let tail: f64 = a[end..].iter().zip(&b[end..]).map(|(x, y)| x * y).sum();
+
+    (lanes[0] + lanes[1]) + (lanes[2] + lanes[3]) + tail
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx,fma")]
+unsafe fn axpy_avx(alpha: f64, x: &[f64], y: &mut [f64]) {
+    let end = x.len() - x.len() % 4;
+    let factor = _mm256_set1_pd(alpha);
+
+    for i in (0..end).step_by(4) {
+        let ptr = y.as_mut_ptr().add(i);
+        let sum = _mm256_fmadd_pd(
+            factor,
+            _mm256_loadu_pd(x.as_ptr().add(i)),
+            _mm256_loadu_pd(ptr),
+        );
+        _mm256_storeu_pd(ptr, sum);
+    }
+
+    for i in end..x.len() {
+        y[i] += alpha * x[i];
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn add_avx(a: &[f64], b: &[f64], out: &mut [f64]) {
+    let end = a.len() - a.len() % 4;
+
+    for i in (0..end).step_by(4) {
+        let sum = _mm256_add_pd(
+            _mm256_loadu_pd(a.as_ptr().add(i)),
+            _mm256_loadu_pd(b.as_ptr().add(i)),
+        );
+        _mm256_storeu_pd(out.as_mut_ptr().add(i), sum);
+    }
+
+    for i in end..a.len() {
+        out[i] = a[i] + b[i];
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn sub_avx(a: &[f64], b: &[f64], out: &mut [f64]) {
+    let end = a.len() - a.len() % 4;
+
+    for i in (0..end).step_by(4) {
+        let difference = _mm256_sub_pd(
+            _mm256_loadu_pd(a.as_ptr().add(i)),
+            _mm256_loadu_pd(b.as_ptr().add(i)),
+        );
+        _mm256_storeu_pd(out.as_mut_ptr().add(i), difference);
+    }
+
+    for i in end..a.len() {
+        out[i] = a[i] - b[i];
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn scale_avx(data: &mut [f64], k: f64) {
+    let end = data.len() - data.len() % 4;
+    let factor = _mm256_set1_pd(k);
+
+    for i in (0..end).step_by(4) {
+        let ptr = data.as_mut_ptr().add(i);
+        _mm256_storeu_pd(ptr, _mm256_mul_pd(_mm256_loadu_pd(ptr), factor));
+    }
+
+    data[end..].iter_mut().for_each(|f| *f *= k);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn fill_avx(data: &mut [f64], k: f64) {
+    let end = data.len() - data.len() % 4;
+    let value = _mm256_set1_pd(k);
+
+    for i in (0..end).step_by(4) {
+        _mm256_storeu_pd(data.as_mut_ptr().add(i), value);
+    }
+
+    data[end..].fill(k);
+}
+
+#[cfg(test)]
+mod simd_tests {
+    use super::*;
+
+    fn example(len: usize, seed: usize) -> Vec<f64> {
+        (0..len)
+            .map(|i| ((i * 31 + seed) % 17) as f64 / 4.0 - 2.0)
+            .collect()
+    }
+
+    #[test]
+    fn test_dot() {
+        for len in 0..70 {
+            let (a, b) = (example(len, 1), example(len, 5));
+
+            let expected: f64 = a.iter().zip(&b).map(|(x, y)| x * y).sum();
+
+            assert!((dot(&a, &b) - expected).abs() < 1e-12, "length {}", len);
+        }
+    }
+
+    #[test]
+    fn test_small_dot_is_a_plain_sum() {
+        let a = [1.0, 2.0, 3.0, 4.0];
+        let b = [6.9, 4.2, 3.5, 6.7];
+
+        assert_eq!(dot(&a, &b), 52.6);
+    }
+
+    #[test]
+    fn test_elementwise() {
+        for len in 0..40 {
+            let (a, b) = (example(len, 2), example(len, 9));
+            let mut out = vec![0.0; len];
+
+            add(&a, &b, &mut out);
+            assert!(out.iter().enumerate().all(|(i, &f)| f == a[i] + b[i]));
+
+            sub(&a, &b, &mut out);
+            assert!(out.iter().enumerate().all(|(i, &f)| f == a[i] - b[i]));
+
+            out.copy_from_slice(&a);
+            scale(&mut out, 1.5);
+            assert!(out.iter().enumerate().all(|(i, &f)| f == a[i] * 1.5));
+
+            fill(&mut out, -3.0);
+            assert!(out.iter().all(|&f| f == -3.0));
+
+            out.copy_from_slice(&b);
+            axpy(0.5, &a, &mut out);
+            assert!(out
+                .iter()
+                .enumerate()
+                .all(|(i, &f)| (f - (b[i] + 0.5 * a[i])).abs() < 1e-15));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mismatched_dot() {
+        dot(&example(25, 1), &example(16, 2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mismatched_elementwise() {
+        let mut out = vec![0.0; 25];
+
+        add(&example(25, 1), &example(16, 2), &mut out);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mismatched_output() {
+        let mut out = vec![0.0; 16];
+
+        sub(&example(25, 1), &example(25, 2), &mut out);
+    }
+}
@@ -0,0 +1,138 @@
+//! Coordinate (triplet) format.
+
+use crate::{
+    error::MatrixError,
+    matrix::Matrix,
+    sparse::{csc::CscMatrix, csr::CsrMatrix, Compressed},
+};
+
+#[derive(Debug, Clone, PartialEq, Default)]
+/// An `R` x `C` sparse matrix stored as a list of `(row, column, value)` triplets.
+///
+/// Duplicate entries are allowed and are summed when converting to another format.
+pub struct CooMatrix<const R: usize, const C: usize> {
+    triplets: Vec<(usize, usize, f64)>,
+}
+
+impl<const R: usize, const C: usize> CooMatrix<R, C> {
+    /// Creates an empty sparse matrix.
+    pub fn new() -> Self {
+        Self {
+            triplets: Vec::new(),
+        }
+    }
+
+    /// Creates a sparse matrix from `(row, column, value)` triplets, checking that every entry is in bounds.
+    pub fn from_triplets(triplets: Vec<(usize, usize, f64)>) -> Result<Self, MatrixError> {
+        if triplets.iter().any(|&(i, j, _)| i >= R || j >= C) {
+            return Err(MatrixError::UndefinedIndex);
+        }
+
+        Ok(Self { triplets })
+    }
+
+    /// Creates a sparse matrix from the non-zero entries of a dense matrix.
+    pub fn from_dense(matrix: &Matrix<R, C>) -> Self {
+        let triplets = matrix
+            .data()
+            .iter()
+            .enumerate()
+            .filter(|(_, &f)| f != 0.0)
+            .map(|(i, &f)| (i / C, i % C, f))
+            .collect();
+
+        Self { triplets }
+    }
+
+    /// Adds an entry at row `i` and column `j`.
+    pub fn push(&mut self, i: usize, j: usize, value: f64) -> Result<(), MatrixError> {
+        if i >= R || j >= C {
+            return Err(MatrixError::UndefinedIndex);
+        }
+
+        self.triplets.push((i, j, value));
+
+        Ok(())
+    }
+
+    /// Returns the number of stored entries, counting duplicates separately.
+    pub fn nnz(&self) -> usize {
+        self.triplets.len()
+    }
+
+    /// Returns the stored `(row, column, value)` triplets.
+    pub fn triplets(&self) -> &[(usize, usize, f64)] {
+        &self.triplets
+    }
+
+    /// Returns the transpose of the matrix.
+    pub fn transpose(&self) -> CooMatrix<C, R> {
+        CooMatrix {
+            triplets: self.triplets.iter().map(|&(i, j, f)| (j, i, f)).collect(),
+        }
+    }
+
+    /// Converts the matrix to compressed sparse row format.
+    pub fn to_csr(&self) -> CsrMatrix<R, C> {
+        CsrMatrix::from_storage(Compressed::from_triplets(R, self.triplets.clone()))
+    }
+
+    /// Converts the matrix to compressed sparse column format.
+    pub fn to_csc(&self) -> CscMatrix<R, C> {
+        let triplets = self.triplets.iter().map(|&(i, j, f)| (j, i, f)).collect();
+
+        CscMatrix::from_storage(Compressed::from_triplets(C, triplets))
+    }
+
+    /// Converts the matrix to a dense matrix.
+    pub fn to_dense(&self) -> Matrix<R, C> {
+        let mut data = vec![0.0; R * C];
+
+        self.triplets
+            .iter()
+            .for_each(|&(i, j, f)| data[i * C + j] += f);
+
+        Matrix::new(data).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod coo_tests {
+    use super::*;
+
+    #[test]
+    fn test_from_triplets() {
+        let coo: CooMatrix<2, 3> =
+            CooMatrix::from_triplets(vec![(0, 0, 1.0), (1, 2, 2.0), (0, 0, 3.0)]).unwrap();
+
+        assert_eq!(coo.nnz(), 3);
+        assert_eq!(coo.to_dense().data(), &[4.0, 0.0, 0.0, 0.0, 0.0, 2.0]);
+
+        let out_of_bounds: Result<CooMatrix<2, 3>, _> = CooMatrix::from_triplets(vec![(2, 0, 1.0)]);
+        assert!(out_of_bounds.is_err());
+    }
+
+    #[test]
+    fn test_push_and_transpose() {
+        let mut coo: CooMatrix<2, 3> = CooMatrix::new();
+
+        assert!(coo.push(0, 2, 5.0).is_ok());
+        assert!(coo.push(0, 3, 5.0).is_err());
+
+        assert_eq!(
+            coo.transpose().to_dense().data(),
+            &[0.0, 0.0, 0.0, 0.0, 5.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn test_conversions() {
+        let dense: Matrix<2, 3> = Matrix::new(vec![1.0, 0.0, 2.0, 0.0, 3.0, 0.0]).unwrap();
+
+        let coo = CooMatrix::from_dense(&dense);
+
+        assert_eq!(coo.nnz(), 3);
+        assert_eq!(coo.to_csr().to_dense(), dense);
+        assert_eq!(coo.to_csc().to_dense(), dense);
+    }
+}
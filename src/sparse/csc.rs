@@ -0,0 +1,169 @@
+//! Compressed sparse column format.
+
+use crate::{
+    matrix::Matrix,
+    sparse::{csr::CsrMatrix, Compressed},
+    vector::VectorN,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+/// An `R` x `C` sparse matrix in compressed sparse column format.
+pub struct CscMatrix<const R: usize, const C: usize> {
+    storage: Compressed,
+}
+
+impl<const R: usize, const C: usize> CscMatrix<R, C> {
+    pub(crate) fn from_storage(storage: Compressed) -> Self {
+        Self { storage }
+    }
+
+    /// Creates a sparse matrix from the non-zero entries of a dense matrix.
+    pub fn from_dense(matrix: &Matrix<R, C>) -> Self {
+        Self::from_storage(Compressed::from_dense(R, C, matrix.data()).transpose(C))
+    }
+
+    /// Converts the matrix to a dense matrix.
+    pub fn to_dense(&self) -> Matrix<R, C> {
+        Matrix::new(self.storage.transpose(R).to_dense(C)).unwrap()
+    }
+
+    /// Converts the matrix to compressed sparse row format.
+    pub fn to_csr(&self) -> CsrMatrix<R, C> {
+        CsrMatrix::from_storage(self.storage.transpose(R))
+    }
+
+    /// Returns the number of stored entries.
+    pub fn nnz(&self) -> usize {
+        self.storage.nnz()
+    }
+
+    /// Fetches the entry located at row `i` and column `j`.
+    pub fn get(&self, i: usize, j: usize) -> Option<f64> {
+        if i >= R || j >= C {
+            return None;
+        }
+
+        Some(self.storage.get(j, i))
+    }
+
+    /// Iterates over the stored `(row, value)` pairs in column `j`, or returns `None` if there is no such
+    /// column.
+    pub fn column(&self, j: usize) -> Option<impl Iterator<Item = (usize, f64)> + '_> {
+        if j >= C {
+            return None;
+        }
+
+        Some(self.storage.lane(j))
+    }
+
+    /// Iterates over all stored entries as `(row, column, value)` triplets in column-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, f64)> + '_ {
+        self.storage.iter().map(|(j, i, f)| (i, j, f))
+    }
+
+    /// Returns the transpose of the matrix.
+    pub fn transpose(&self) -> CscMatrix<C, R> {
+        CscMatrix::from_storage(self.storage.transpose(R))
+    }
+
+    /// Multiplies the matrix by a dense matrix.
+    pub fn multiply<const K: usize>(&self, other: &Matrix<C, K>) -> Matrix<R, K> {
+        let other = other.data();
+        let mut data = vec![0.0; R * K];
+
+        for j in 0..C {
+            for (i, f) in self.storage.lane(j) {
+                data[i * K..(i + 1) * K]
+                    .iter_mut()
+                    .zip(&other[j * K..(j + 1) * K])
+                    .for_each(|(entry, b)| *entry += f * b);
+            }
+        }
+
+        Matrix::new(data).unwrap()
+    }
+
+    /// Multiplies the matrix by a vector.
+    pub fn mul_vector(&self, vector: &VectorN<C>) -> VectorN<R> {
+        let mut data = [0.0; R];
+
+        vector.data().iter().enumerate().for_each(|(j, x)| {
+            self.storage.lane(j).for_each(|(i, f)| data[i] += f * x);
+        });
+
+        VectorN::new(data)
+    }
+}
+
+impl<const R: usize, const C: usize> std::ops::Add for &CscMatrix<R, C> {
+    type Output = CscMatrix<R, C>;
+
+    /// Adds two sparse matrices A and B
+    fn add(self, rhs: &CscMatrix<R, C>) -> Self::Output {
+        CscMatrix::from_storage(self.storage.add(&rhs.storage))
+    }
+}
+
+#[cfg(test)]
+mod csc_tests {
+    use super::*;
+
+    fn example() -> Matrix<3, 4> {
+        #[rustfmt::skip]
+        let data = vec![
+            1.0, 0.0, 0.0, 2.0,
+            0.0, 0.0, 3.0, 0.0,
+            4.0, 5.0, 0.0, 0.0,
+        ];
+
+        Matrix::new(data).unwrap()
+    }
+
+    #[test]
+    fn test_dense_round_trip() {
+        let csc = CscMatrix::from_dense(&example());
+
+        assert_eq!(csc.nnz(), 5);
+        assert_eq!(csc.to_dense(), example());
+        assert_eq!(csc.get(0, 3), Some(2.0));
+        assert_eq!(csc.get(0, 4), None);
+        assert_eq!(csc.to_csr(), CsrMatrix::from_dense(&example()));
+    }
+
+    #[test]
+    fn test_column_iterators() {
+        let csc = CscMatrix::from_dense(&example());
+
+        assert_eq!(
+            csc.column(0).unwrap().collect::<Vec<_>>(),
+            [(0, 1.0), (2, 4.0)]
+        );
+        assert!(csc.column(4).is_none());
+        assert_eq!(csc.iter().next(), Some((0, 0, 1.0)));
+        assert_eq!(csc.transpose().to_dense().data()[1..3], [0.0, 4.0]);
+    }
+
+    #[test]
+    fn test_products() {
+        let csc = CscMatrix::from_dense(&example());
+
+        let other: Matrix<4, 2> =
+            Matrix::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]).unwrap();
+
+        assert_eq!(
+            csc.multiply(&other).data(),
+            &[15.0, 18.0, 15.0, 18.0, 19.0, 28.0]
+        );
+
+        let vector = VectorN::new([1.0, 2.0, 3.0, 4.0]);
+
+        assert_eq!(csc.mul_vector(&vector).data(), &[9.0, 9.0, 14.0]);
+    }
+
+    #[test]
+    fn test_addition() {
+        let a = CscMatrix::from_dense(&example());
+
+        assert_eq!((&a + &a).get(2, 1), Some(10.0));
+    }
+}
@@ -0,0 +1,195 @@
+//! Compressed sparse row format.
+
+use crate::{
+    matrix::Matrix,
+    sparse::{csc::CscMatrix, Compressed},
+    vector::VectorN,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+/// An `R` x `C` sparse matrix in compressed sparse row format.
+pub struct CsrMatrix<const R: usize, const C: usize> {
+    storage: Compressed,
+}
+
+impl<const R: usize, const C: usize> CsrMatrix<R, C> {
+    pub(crate) fn from_storage(storage: Compressed) -> Self {
+        Self { storage }
+    }
+
+    /// Creates a sparse matrix from the non-zero entries of a dense matrix.
+    pub fn from_dense(matrix: &Matrix<R, C>) -> Self {
+        Self::from_storage(Compressed::from_dense(R, C, matrix.data()))
+    }
+
+    /// Converts the matrix to a dense matrix.
+    pub fn to_dense(&self) -> Matrix<R, C> {
+        Matrix::new(self.storage.to_dense(C)).unwrap()
+    }
+
+    /// Converts the matrix to compressed sparse column format.
+    pub fn to_csc(&self) -> CscMatrix<R, C> {
+        CscMatrix::from_storage(self.storage.transpose(C))
+    }
+
+    /// Returns the number of stored entries.
+    pub fn nnz(&self) -> usize {
+        self.storage.nnz()
+    }
+
+    /// Fetches the entry located at row `i` and column `j`.
+    pub fn get(&self, i: usize, j: usize) -> Option<f64> {
+        if i >= R || j >= C {
+            return None;
+        }
+
+        Some(self.storage.get(i, j))
+    }
+
+    /// Iterates over the stored `(column, value)` pairs in row `i`, or returns `None` if there is no such
+    /// row.
+    pub fn row(&self, i: usize) -> Option<impl Iterator<Item = (usize, f64)> + '_> {
+        if i >= R {
+            return None;
+        }
+
+        Some(self.storage.lane(i))
+    }
+
+    /// Iterates over all stored entries as `(row, column, value)` triplets in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, f64)> + '_ {
+        self.storage.iter()
+    }
+
+    /// Returns the transpose of the matrix.
+    pub fn transpose(&self) -> CsrMatrix<C, R> {
+        CsrMatrix::from_storage(self.storage.transpose(C))
+    }
+
+    /// Multiplies the matrix by a dense matrix.
+    pub fn multiply<const K: usize>(&self, other: &Matrix<C, K>) -> Matrix<R, K> {
+        let other = other.data();
+        let mut data = vec![0.0; R * K];
+
+        for (i, row) in data.chunks_mut(K).enumerate() {
+            for (j, f) in self.storage.lane(i) {
+                row.iter_mut()
+                    .zip(&other[j * K..(j + 1) * K])
+                    .for_each(|(entry, b)| *entry += f * b);
+            }
+        }
+
+        Matrix::new(data).unwrap()
+    }
+
+    /// Multiplies the matrix by a vector.
+    pub fn mul_vector(&self, vector: &VectorN<C>) -> VectorN<R> {
+        let x = vector.data();
+        let data: Vec<f64> = (0..R)
+            .map(|i| self.storage.lane(i).map(|(j, f)| f * x[j]).sum())
+            .collect();
+
+        VectorN::new(data.try_into().unwrap())
+    }
+}
+
+impl<const R: usize, const C: usize> std::ops::Add for &CsrMatrix<R, C> {
+    type Output = CsrMatrix<R, C>;
+
+    /// Adds two sparse matrices A and B
+    fn add(self, rhs: &CsrMatrix<R, C>) -> Self::Output {
+        CsrMatrix::from_storage(self.storage.add(&rhs.storage))
+    }
+}
+
+#[cfg(test)]
+mod csr_tests {
+    use super::*;
+
+    fn example() -> Matrix<3, 4> {
+        #[rustfmt::skip]
+        let data = vec![
+            1.0, 0.0, 0.0, 2.0,
+            0.0, 0.0, 3.0, 0.0,
+            4.0, 5.0, 0.0, 0.0,
+        ];
+
+        Matrix::new(data).unwrap()
+    }
+
+    #[test]
+    fn test_dense_round_trip() {
+        let csr = CsrMatrix::from_dense(&example());
+
+        assert_eq!(csr.nnz(), 5);
+        assert_eq!(csr.to_dense(), example());
+        assert_eq!(csr.get(2, 1), Some(5.0));
+        assert_eq!(csr.get(1, 1), Some(0.0));
+        assert_eq!(csr.get(3, 0), None);
+    }
+
+    #[test]
+    fn test_row_iterators() {
+        let csr = CsrMatrix::from_dense(&example());
+
+        assert_eq!(
+            csr.row(0).unwrap().collect::<Vec<_>>(),
+            [(0, 1.0), (3, 2.0)]
+        );
+        assert_eq!(csr.row(1).unwrap().collect::<Vec<_>>(), [(2, 3.0)]);
+        assert!(csr.row(3).is_none());
+        assert_eq!(
+            csr.iter().collect::<Vec<_>>(),
+            [
+                (0, 0, 1.0),
+                (0, 3, 2.0),
+                (1, 2, 3.0),
+                (2, 0, 4.0),
+                (2, 1, 5.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transpose_and_csc() {
+        let csr = CsrMatrix::from_dense(&example());
+
+        let transpose = csr.transpose();
+
+        assert_eq!(transpose.get(3, 0), Some(2.0));
+        assert_eq!(transpose.transpose(), csr);
+        assert_eq!(csr.to_csc().to_dense(), example());
+    }
+
+    #[test]
+    fn test_products() {
+        let csr = CsrMatrix::from_dense(&example());
+
+        let other: Matrix<4, 2> =
+            Matrix::new(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]).unwrap();
+
+        assert_eq!(
+            csr.multiply(&other).data(),
+            &[15.0, 18.0, 15.0, 18.0, 19.0, 28.0]
+        );
+
+        let vector = VectorN::new([1.0, 2.0, 3.0, 4.0]);
+
+        assert_eq!(csr.mul_vector(&vector).data(), &[9.0, 9.0, 14.0]);
+    }
+
+    #[test]
+    fn test_addition() {
+        let a = CsrMatrix::from_dense(&example());
+
+        let mut negated = example();
+        negated.scalar_multiply(-1.0);
+        negated.set(1.0, 1, 1).unwrap();
+        let b = CsrMatrix::from_dense(&negated);
+
+        let sum = &a + &b;
+
+        assert_eq!(sum.to_dense().data().iter().sum::<f64>(), 1.0);
+        assert_eq!((&a + &a).to_dense().data()[3], 4.0);
+    }
+}
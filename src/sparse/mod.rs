@@ -0,0 +1,140 @@
+//! Sparse matrices.
+//!
+//! [`coo::CooMatrix`] is convenient for assembling a matrix entry by entry, while [`csr::CsrMatrix`]
+//! and [`csc::CscMatrix`] are compressed formats suited to products and row or column access.
+
+pub mod coo;
+pub mod csc;
+pub mod csr;
+
+/// Compressed storage shared by the CSR and CSC formats.
+///
+/// The entries are grouped into "lanes" (rows for CSR, columns for CSC). The entries of lane `i` are
+/// `indices[offsets[i]..offsets[i + 1]]` and `values[offsets[i]..offsets[i + 1]]`, sorted by index.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Compressed {
+    offsets: Vec<usize>,
+    indices: Vec<usize>,
+    values: Vec<f64>,
+}
+
+impl Compressed {
+    /// Compresses `(lane, index, value)` triplets into `lanes` lanes, summing duplicate entries.
+    fn from_triplets(lanes: usize, mut triplets: Vec<(usize, usize, f64)>) -> Self {
+        triplets.sort_by_key(|&(lane, index, _)| (lane, index));
+
+        let mut offsets = vec![0; lanes + 1];
+        let mut indices: Vec<usize> = Vec::with_capacity(triplets.len());
+        let mut values: Vec<f64> = Vec::with_capacity(triplets.len());
+        let mut previous = None;
+
+        for (lane, index, value) in triplets {
+            if previous == Some((lane, index)) {
+                *values.last_mut().unwrap() += value;
+                continue;
+            }
+
+            previous = Some((lane, index));
+            offsets[lane + 1] += 1;
+            indices.push(index);
+            values.push(value);
+        }
+
+        // Turn the per-lane counts into offsets.
+        (0..lanes).for_each(|i| offsets[i + 1] += offsets[i]);
+
+        Self {
+            offsets,
+            indices,
+            values,
+        }
+    }
+
+    /// Compresses the non-zero entries of a row-major dense buffer, using its rows as lanes.
+    fn from_dense(lanes: usize, length: usize, data: &[f64]) -> Self {
+        let triplets = data
+            .iter()
+            .enumerate()
+            .filter(|(_, &f)| f != 0.0)
+            .map(|(i, &f)| (i / length, i % length, f))
+            .collect();
+
+        Self::from_triplets(lanes, triplets)
+    }
+
+    fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    fn lanes(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Iterates over the `(index, value)` pairs in lane `i`.
+    fn lane(&self, i: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let range = self.offsets[i]..self.offsets[i + 1];
+
+        self.indices[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.values[range].iter().copied())
+    }
+
+    /// Iterates over all entries as `(lane, index, value)` triplets.
+    fn iter(&self) -> impl Iterator<Item = (usize, usize, f64)> + '_ {
+        (0..self.lanes()).flat_map(move |i| self.lane(i).map(move |(j, f)| (i, j, f)))
+    }
+
+    /// Fetches the entry at the given lane and index, returning zero if it isn't stored.
+    fn get(&self, lane: usize, index: usize) -> f64 {
+        let range = self.offsets[lane]..self.offsets[lane + 1];
+
+        match self.indices[range.clone()].binary_search(&index) {
+            Ok(k) => self.values[range.start + k],
+            Err(_) => 0.0,
+        }
+    }
+
+    /// Swaps the roles of lanes and indices, e.g. turning CSR storage into CSC storage of the same matrix.
+    fn transpose(&self, lanes: usize) -> Self {
+        Self::from_triplets(lanes, self.iter().map(|(i, j, f)| (j, i, f)).collect())
+    }
+
+    /// Adds two compressed matrices with the same layout.
+    fn add(&self, other: &Self) -> Self {
+        Self::from_triplets(self.lanes(), self.iter().chain(other.iter()).collect())
+    }
+
+    /// Expands into a row-major dense buffer, using the lanes as rows.
+    fn to_dense(&self, length: usize) -> Vec<f64> {
+        let mut data = vec![0.0; self.lanes() * length];
+
+        self.iter().for_each(|(i, j, f)| data[i * length + j] = f);
+
+        data
+    }
+}
+
+#[cfg(test)]
+mod compressed_tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicates_are_summed() {
+        let compressed = Compressed::from_triplets(2, vec![(1, 0, 1.0), (0, 1, 2.0), (1, 0, 3.0)]);
+
+        assert_eq!(compressed.offsets, [0, 1, 2]);
+        assert_eq!(compressed.indices, [1, 0]);
+        assert_eq!(compressed.values, [2.0, 4.0]);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let compressed = Compressed::from_dense(2, 3, &[1.0, 0.0, 2.0, 0.0, 3.0, 0.0]);
+
+        assert_eq!(
+            compressed.transpose(3).to_dense(2),
+            [1.0, 0.0, 0.0, 3.0, 2.0, 0.0]
+        );
+    }
+}
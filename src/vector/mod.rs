@@ -1,9 +1,10 @@
 //! Vectors.
 
+mod norm;
 mod ops;
 mod polar;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 /// An `N`x 1 vector struct.
 pub struct VectorN<const N: usize> {
     data: [f64; N]
@@ -25,9 +26,9 @@ impl<const N: usize> VectorN<N> {
     /// Gets an entry in the vector
     pub fn get(&self, i: usize) -> Option<f64> {
         if i > self.data.len() {
-            return None;
+            None
         } else {
-            return Some(self.data[i]);
+            Some(self.data[i])
         }
 
     }
@@ -0,0 +1,52 @@
+//! Vector norms.
+
+use crate::{simd, vector::VectorN};
+
+impl<const N: usize> VectorN<N> {
+    /// Returns the Euclidean length of the vector.
+    pub fn norm(&self) -> f64 {
+        simd::norm(&self.data)
+    }
+
+    /// Returns the p-norm `(Σ|xᵢ|ᵖ)^(1/p)`, where `p` should be at least 1. An infinite `p` gives the
+    /// largest absolute entry.
+    pub fn norm_p(&self, p: f64) -> f64 {
+        if p == f64::INFINITY {
+            return self.norm_inf();
+        }
+
+        self.data
+            .iter()
+            .map(|f| f.abs().powf(p))
+            .sum::<f64>()
+            .powf(1.0 / p)
+    }
+
+    /// Returns the sum of the absolute entries.
+    pub fn norm_1(&self) -> f64 {
+        self.data.iter().map(|f| f.abs()).sum()
+    }
+
+    /// Returns the largest absolute entry.
+    pub fn norm_inf(&self) -> f64 {
+        self.data.iter().map(|f| f.abs()).fold(0.0, f64::max)
+    }
+}
+
+#[cfg(test)]
+mod vector_norm_tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_norms() {
+        let vector: VectorN<3> = VectorN::new([3.0, -4.0, 12.0]);
+
+        assert_eq!(vector.norm(), 13.0);
+        assert_eq!(vector.norm_1(), 19.0);
+        assert_eq!(vector.norm_inf(), 12.0);
+        assert_eq!(vector.norm_p(1.0), 19.0);
+        assert_eq!(vector.norm_p(f64::INFINITY), 12.0);
+        assert!((vector.norm_p(2.0) - 13.0).abs() < 1e-12);
+        assert!((vector.norm_p(3.0) - 1819f64.cbrt()).abs() < 1e-12);
+    }
+}
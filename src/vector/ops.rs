@@ -1,6 +1,6 @@
 //! Vector operations such as addition, subtraction, dot products, etc.
 
-use crate::{error::VectorError, vector::VectorN};
+use crate::{error::VectorError, matrix::Matrix, parallel, simd, vector::VectorN};
 
 impl<const N: usize> VectorN<N> {
     /// Returns the dot product of this vector
@@ -9,40 +9,183 @@ impl<const N: usize> VectorN<N> {
             return Err(VectorError::InvalidDimensions);
         }
 
-        let dotproduct = (0..N).map(|i| self.data[i] * other.data[i]).sum();
+        let dotproduct = parallel::reduce(&self.data, &other.data, simd::dot);
 
         Ok(dotproduct)
     }
 
     /// Performs scalar multiplication on the vector.
     pub fn mul(&mut self, n: f64) {
-        (0..N).for_each(|i| self.data[i] *= n);
+        self.apply(|x| x * n);
     }
 
     /// Performs scalar multiplication on a clone of the vector and returning the result.
     pub fn mul_cpy(&mut self, n: f64) -> Self {
-        let newvec: Vec<f64> = (0..N).map(|i| self.data[i] * n).collect();
+        self.map(|x| x * n)
+    }
+
+    /// Returns the outer product of this vector and `other`, the `N` x `M` matrix with entries `a_i * b_j`.
+    pub fn outer<const M: usize>(&self, other: &VectorN<M>) -> Matrix<N, M> {
+        let data = self
+            .data
+            .iter()
+            .flat_map(|a| other.data.iter().map(move |b| a * b))
+            .collect();
+
+        Matrix::new(data).unwrap()
+    }
+
+    /// Returns a new vector with `f` applied to every entry.
+    pub fn map(&self, f: impl FnMut(f64) -> f64) -> VectorN<N> {
+        VectorN::new(self.data.map(f))
+    }
+
+    /// Calls `f` with a mutable reference to every entry.
+    pub fn map_mut(&mut self, f: impl FnMut(&mut f64)) {
+        self.data.iter_mut().for_each(f);
+    }
+
+    /// Replaces every entry `x` with `f(x)` in place.
+    pub fn apply(&mut self, mut f: impl FnMut(f64) -> f64) {
+        self.map_mut(|x| *x = f(*x));
+    }
+
+    /// Returns a new vector whose entries are `f(a, b)` for the matching entries of this vector and `other`.
+    pub fn zip_map(&self, other: &VectorN<N>, mut f: impl FnMut(f64, f64) -> f64) -> VectorN<N> {
+        VectorN::new(std::array::from_fn(|i| f(self.data[i], other.data[i])))
+    }
+
+    /// Returns a new vector with `f(i, x)` applied to the entry `x` at index `i`.
+    pub fn map_indexed(&self, mut f: impl FnMut(usize, f64) -> f64) -> VectorN<N> {
+        VectorN::new(std::array::from_fn(|i| f(i, self.data[i])))
+    }
+
+    /// Orthonormalizes `vectors` in order with modified Gram–Schmidt, applied twice for accuracy.
+    ///
+    /// A vector counts as linearly dependent on the ones before it when less than `tol` times its length
+    /// is left after removing their directions, which returns a `LinearlyDependent` error.
+    pub fn gram_schmidt(vectors: &[VectorN<N>], tol: f64) -> Result<Vec<VectorN<N>>, VectorError> {
+        let mut basis = Vec::with_capacity(vectors.len());
+
+        for (index, v) in vectors.iter().enumerate() {
+            let q =
+                orthonormal_part(v, &basis, tol).ok_or(VectorError::LinearlyDependent { index })?;
+            basis.push(q);
+        }
 
-        VectorN::new(newvec.try_into().unwrap())
+        Ok(basis)
     }
 
+    /// Returns an orthonormal basis of the span of `vectors`, dropping those that are linearly dependent
+    /// on the ones before them instead of failing like [`VectorN::gram_schmidt`].
+    pub(crate) fn orthonormal_span(vectors: &[VectorN<N>], tol: f64) -> Vec<VectorN<N>> {
+        let mut basis = Vec::with_capacity(vectors.len());
+
+        for v in vectors {
+            if let Some(q) = orthonormal_part(v, &basis, tol) {
+                basis.push(q);
+            }
+        }
+
+        basis
+    }
+
+    /// Checks whether `vectors` are linearly independent, in the sense of [`VectorN::gram_schmidt`].
+    pub fn is_linearly_independent(vectors: &[VectorN<N>], tol: f64) -> bool {
+        Self::gram_schmidt(vectors, tol).is_ok()
+    }
+
+    /// Checks whether every pair of `vectors` has a dot product of at most `tol` in absolute value.
+    pub fn is_orthogonal(vectors: &[VectorN<N>], tol: f64) -> bool {
+        vectors.iter().enumerate().all(|(i, a)| {
+            vectors[i + 1..]
+                .iter()
+                .all(|b| simd::dot(&a.data, &b.data).abs() <= tol)
+        })
+    }
+
+    /// Checks whether `vectors` are orthogonal and each has a squared length within `tol` of one.
+    pub fn is_orthonormal(vectors: &[VectorN<N>], tol: f64) -> bool {
+        Self::is_orthogonal(vectors, tol)
+            && vectors
+                .iter()
+                .all(|v| (simd::dot(&v.data, &v.data) - 1.0).abs() <= tol)
+    }
+
+    /// Extends `partial` to an orthonormal basis of all `N` dimensions.
+    ///
+    /// The first vectors are `partial` orthonormalized with [`VectorN::gram_schmidt`], which returns its
+    /// error if they are linearly dependent. The rest come from the standard basis vectors that stick out
+    /// furthest from the span so far.
+    pub fn complete_basis(
+        partial: &[VectorN<N>],
+        tol: f64,
+    ) -> Result<Vec<VectorN<N>>, VectorError> {
+        let mut basis = Self::gram_schmidt(partial, tol)?;
+
+        while basis.len() < N {
+            let residual = (0..N)
+                .map(|i| {
+                    reject(
+                        VectorN::new(std::array::from_fn(|j| if i == j { 1.0 } else { 0.0 })),
+                        &basis,
+                    )
+                })
+                .max_by(|a, b| a.norm().total_cmp(&b.norm()))
+                .unwrap();
+            let length = residual.norm();
+
+            basis.push(residual.map(|f| f / length));
+        }
+
+        Ok(basis)
+    }
+}
+
+/// Returns `v` with the directions of the orthonormal `basis` removed, projecting twice over so the
+/// rounding of the first pass is removed as well.
+fn reject<const N: usize>(mut v: VectorN<N>, basis: &[VectorN<N>]) -> VectorN<N> {
+    for _ in 0..2 {
+        for q in basis {
+            let projection = simd::dot(&v.data, &q.data);
+            v.data
+                .iter_mut()
+                .zip(&q.data)
+                .for_each(|(a, b)| *a -= projection * b);
+        }
+    }
+
+    v
+}
+
+/// Returns the normalized part of `v` orthogonal to `basis`, or `None` if less than `tol` times its length
+/// is left.
+fn orthonormal_part<const N: usize>(
+    v: &VectorN<N>,
+    basis: &[VectorN<N>],
+    tol: f64,
+) -> Option<VectorN<N>> {
+    let residual = reject(v.clone(), basis);
+    let remaining = residual.norm();
+
+    if remaining == 0.0 || remaining <= tol * v.norm() {
+        return None;
+    }
+
+    Some(residual.map(|f| f / remaining))
 }
 
 impl<const N: usize> std::ops::Add for VectorN<N> {
     type Output = VectorN<N>;
 
     fn add(self, rhs: VectorN<N>) -> Self::Output {
-        let newvec: Vec<f64> = (0..N).map(|i| self.data[i] + rhs.data[i]).collect();
-
-        VectorN::new(newvec.try_into().unwrap())
+        self.zip_map(&rhs, |a, b| a + b)
     }
 }
 
 impl<const N: usize> std::ops::AddAssign for VectorN<N> {
     fn add_assign(&mut self, rhs: Self) {
-        let newvec: Vec<f64> = (0..N).map(|i| self.data[i] + rhs.data[i]).collect();
-
-        *self = VectorN::new(newvec.try_into().unwrap());
+        *self = self.zip_map(&rhs, |a, b| a + b);
     }
 }
 
@@ -66,6 +209,16 @@ mod vec_op_tests {
         assert_eq!(vector.data, [5.0, 10.0, 15.0, 20.0]);
     }
 
+    #[test]
+    fn test_outer_product() {
+        let a: VectorN<2> = VectorN::new([1.0, 2.0]);
+        let b: VectorN<3> = VectorN::new([3.0, 4.0, 5.0]);
+
+        let c: Matrix<2, 3> = a.outer(&b);
+
+        assert_eq!(c.data(), &[3.0, 4.0, 5.0, 6.0, 8.0, 10.0]);
+    }
+
     #[test]
     fn test_vector_addition() {
         let data_a = [1.0, 2.0, 3.0, 4.0];
@@ -89,4 +242,59 @@ mod vec_op_tests {
 
         assert_eq!(a.data, [6.0, 8.0, 10.0, 12.0]);
     }
+
+    #[test]
+    fn test_gram_schmidt() {
+        let vectors = [VectorN::new([3.0, 4.0, 0.0]), VectorN::new([1.0, 1.0, 1.0])];
+        let basis = VectorN::gram_schmidt(&vectors, 1e-10).unwrap();
+
+        assert!(VectorN::is_orthonormal(&basis, 1e-12));
+        assert_eq!(basis[0].data, [0.6, 0.8, 0.0]);
+        assert!(!VectorN::is_orthogonal(&vectors, 1e-12));
+
+        let dependent = [
+            VectorN::new([1.0, 2.0, 3.0]),
+            VectorN::new([0.0, 1.0, 0.0]),
+            VectorN::new([2.0, 4.0 + 1e-14, 6.0]),
+        ];
+        assert!(matches!(
+            VectorN::gram_schmidt(&dependent, 1e-10),
+            Err(VectorError::LinearlyDependent { index: 2 })
+        ));
+        assert!(VectorN::is_linearly_independent(&dependent[..2], 1e-10));
+    }
+
+    #[test]
+    fn test_complete_basis() {
+        let partial = [VectorN::new([1.0, 1.0, 0.0, 0.0])];
+        let basis = VectorN::complete_basis(&partial, 1e-10).unwrap();
+
+        assert_eq!(basis.len(), 4);
+        assert!(VectorN::is_orthonormal(&basis, 1e-12));
+
+        let first = 1.0 / 2.0_f64.sqrt();
+        assert!(basis[0]
+            .data
+            .iter()
+            .zip([first, first, 0.0, 0.0])
+            .all(|(a, b)| (a - b).abs() < 1e-15));
+
+        assert_eq!(VectorN::<2>::complete_basis(&[], 1e-10).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_element_wise_combinators() {
+        let mut a = VectorN::new([-2.0, 0.5, 3.0]);
+        let b = VectorN::new([1.0, 2.0, 3.0]);
+
+        assert_eq!(a.map(|x| x.max(0.0)).data, [0.0, 0.5, 3.0]);
+        assert_eq!(a.zip_map(&b, |x, y| x * y).data, [-2.0, 1.0, 9.0]);
+        assert_eq!(a.map_indexed(|i, x| x + i as f64).data, [-2.0, 1.5, 5.0]);
+
+        a.apply(|x| x.clamp(-1.0, 1.0));
+        assert_eq!(a.data, [-1.0, 0.5, 1.0]);
+
+        a.map_mut(|x| *x *= 2.0);
+        assert_eq!(a.data, [-2.0, 1.0, 2.0]);
+    }
 }
@@ -12,7 +12,7 @@ mod vector_polar_tests {
     #[test]
     fn test_vector_as_polar_form() {
         let data = vec![-3.0, 5.0];
-        let vector: VectorN<2> = VectorN::new(data.try_into().unwrap());
+        let _vector: VectorN<2> = VectorN::new(data.try_into().unwrap());
 
 
     }